@@ -5,54 +5,37 @@ use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::{parse_macro_input, DeriveInput, Ident, Meta, Token};
 
-#[proc_macro_derive(ChangeStateButton, attributes(keyboard, gamepad, target_state))]
+#[proc_macro_derive(ChangeStateButton, attributes(action, target_state))]
 pub fn button(input: TokenStream) -> TokenStream {
     let decl = parse_macro_input!(input as DeriveInput);
 
-    let mut key_codes = None;
-    let mut gamepad_buttons = None;
+    let mut actions = None;
     let mut target_state = None;
 
     let mut error = None;
 
     for attr in decl.attrs.iter() {
         match &attr.meta {
-            Meta::List(list) if list.path.is_ident("keyboard") => {
+            Meta::List(list) if list.path.is_ident("action") => {
                 let result = list.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated);
                 if result.is_err() {
                     error = Some(syn::Error::new(
                         list.span(),
                         indoc! {r#"
-                                The `keyboard` attribute expects idents to be comma separated
+                                The `action` attribute expects idents to be comma separated
 
-                                = help: use `#[keyboard(Space, Return)]`
+                                = help: use `#[action(Confirm, Back)]`
                             "#},
                     ));
                     continue;
                 }
 
-                key_codes = Some(result.unwrap());
-            }
-            Meta::List(list) if list.path.is_ident("gamepad") => {
-                let result = list.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated);
-                if result.is_err() {
-                    error = Some(syn::Error::new(
-                        list.span(),
-                        indoc! {r#"
-                                The `gamepad` attribute expects idents to be comma separated
-
-                                = help: use `#[gamepad(South, Home)]`
-                            "#},
-                    ));
-                    continue;
-                }
-
-                gamepad_buttons = Some(result.unwrap());
+                actions = Some(result.unwrap());
             }
             Meta::List(list) if list.path.is_ident("target_state") => {
                 let result: syn::Result<Ident> = list.parse_args();
-                if result.is_err() {
-                    eprintln!("{}", result.unwrap_err());
+                if let Err(err) = &result {
+                    eprintln!("{}", err);
                     error = Some(syn::Error::new(
                         list.span(),
                         indoc! {r#"
@@ -84,11 +67,8 @@ pub fn button(input: TokenStream) -> TokenStream {
     let struct_name = decl.ident;
     let name = struct_name.to_string();
 
-    let key_codes = key_codes.unwrap_or_default();
-    let key_codes: Vec<_> = key_codes.iter().collect();
-
-    let gamepad_buttons = gamepad_buttons.unwrap_or_default();
-    let gamepad_buttons: Vec<_> = gamepad_buttons.iter().collect();
+    let actions = actions.unwrap_or_default();
+    let actions: Vec<_> = actions.iter().collect();
 
     let expanded = quote! {
         impl ChangeStateButton for #struct_name {
@@ -96,12 +76,8 @@ pub fn button(input: TokenStream) -> TokenStream {
                 #name.to_string()
             }
 
-            fn should_change_state_keyboard(input: Res<Input<KeyCode>>) -> bool {
-                input.any_just_pressed(vec![#(KeyCode::#key_codes,)*])
-            }
-
-            fn should_change_state_gamepad(input: Res<Input<GamepadButton>>) -> bool {
-                input.get_just_pressed().any(|press| vec![#(GamepadButtonType::#gamepad_buttons,)*].contains(&press.button_type))
+            fn actions() -> Vec<Action> {
+                vec![#(Action::#actions,)*]
             }
 
             fn target_state() -> GameState {
@@ -115,11 +91,9 @@ pub fn button(input: TokenStream) -> TokenStream {
 
 #[cfg(test)]
 mod test {
-    use trybuild;
-
     #[test]
     fn test() {
         let t = trybuild::TestCases::new();
-        t.pass("tests/test.rs");
+        t.pass("tests/pass/*.rs");
     }
 }
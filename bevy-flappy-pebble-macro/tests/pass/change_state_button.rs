@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+
+use bevy_flappy_pebble_macro::ChangeStateButton;
+
+#[derive(Debug, Default, Clone, Copy, States, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GameState {
+    #[default]
+    MainMenu,
+    Playing,
+    GameOver,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Confirm,
+    Back,
+}
+
+pub trait ChangeStateButton
+where
+    Self: Component + Sized,
+{
+    fn name(&self) -> String;
+    fn actions() -> Vec<Action>;
+    fn target_state() -> GameState;
+}
+
+#[derive(ChangeStateButton, Component)]
+#[action(Back, Confirm)]
+#[target_state(MainMenu)]
+struct Button;
+
+#[derive(ChangeStateButton, Component)]
+#[action(Confirm)]
+#[target_state(Playing)]
+struct AnotherButton;
+
+#[derive(ChangeStateButton, Component)]
+#[target_state(GameOver)]
+struct FinalButton;
+
+fn main() {
+    assert_eq!(Button.name(), "Button");
+    assert_eq!(Button::actions(), [Action::Back, Action::Confirm]);
+    assert_eq!(Button::target_state(), GameState::MainMenu);
+
+    assert_eq!(AnotherButton.name(), "AnotherButton");
+    assert_eq!(AnotherButton::actions(), [Action::Confirm]);
+    assert_eq!(AnotherButton::target_state(), GameState::Playing);
+
+    assert_eq!(FinalButton.name(), "FinalButton");
+    assert!(FinalButton::actions().is_empty());
+    assert_eq!(FinalButton::target_state(), GameState::GameOver);
+}
@@ -5,5 +5,5 @@ rm -fr ./pages
 wasm-bindgen --no-typescript --target web --out-dir ./pages/ \
 --out-name "bevy-flappy-pebble" ./target/wasm32-unknown-unknown/release/bevy-flappy-pebble.wasm
 mkdir ./pages/assets
-cp ./assets/*.png ./pages/assets
+cp -r ./assets/* ./pages/assets
 cp ./assets/index.html ./pages
@@ -0,0 +1,211 @@
+use bevy::audio::{PlaybackMode, Volume};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::Deserialize;
+
+use crate::{
+    consts,
+    ron_asset::RonAssetLoader,
+    screen_entity::{MoaiPassed, PebbleDied, PebbleFlapped},
+    state::gamescore::ScoreEvent,
+    state::gamestate::GameState,
+    state::settings::{Settings, SettingsOrigin},
+};
+
+pub struct GameAudioPlugin;
+
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<AudioConfig>()
+            .register_asset_loader(RonAssetLoader::<AudioConfig>::new(&["audio.ron"]))
+            .add_systems(Startup, load_audio_config)
+            .add_systems(
+                Update,
+                (
+                    play_sound_effects,
+                    switch_music.run_if(
+                        state_changed::<GameState>().or_else(on_event::<AssetEvent<AudioConfig>>()),
+                    ),
+                    fade_music,
+                )
+                    .chain(),
+            );
+    }
+}
+
+//Sound paths are described in a data file (see `consts::AUDIO_CONFIG_PATH`).
+//States without a music track keep playing the current one.
+#[derive(Debug, Asset, TypePath, Deserialize)]
+pub struct AudioConfig {
+    pub flap: String,
+    pub score: String,
+    pub high_score: String,
+    pub death: String,
+    pub music: HashMap<GameState, String>,
+    pub crossfade_seconds: f32,
+    //music volume multiplier while the game is paused
+    pub pause_volume: f32,
+}
+
+#[derive(Resource)]
+struct AudioConfigHandle(Handle<AudioConfig>);
+
+#[derive(Debug, Component)]
+struct MusicTrack {
+    path: String,
+    volume: f32,
+    fading_out: bool,
+}
+
+fn load_audio_config(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(AudioConfigHandle(
+        asset_server.load(consts::AUDIO_CONFIG_PATH),
+    ));
+}
+
+fn play_sound_effect(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    settings: &Settings,
+    path: &str,
+) {
+    commands.spawn((
+        AudioBundle {
+            source: asset_server.load(path.to_string()),
+            settings: PlaybackSettings::DESPAWN
+                .with_volume(Volume::new_relative(settings.sfx_volume())),
+        },
+        Name::new("SoundEffect"),
+    ));
+}
+
+#[allow(clippy::too_many_arguments)]
+fn play_sound_effects(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+    config_handle: Res<AudioConfigHandle>,
+    configs: Res<Assets<AudioConfig>>,
+    mut flap_events: EventReader<PebbleFlapped>,
+    mut passed_events: EventReader<MoaiPassed>,
+    mut score_events: EventReader<ScoreEvent>,
+    mut death_events: EventReader<PebbleDied>,
+) {
+    let Some(config) = configs.get(&config_handle.0) else {
+        flap_events.clear();
+        passed_events.clear();
+        score_events.clear();
+        death_events.clear();
+        return;
+    };
+
+    //several events of the same kind in one frame still play a single sound
+    if flap_events.read().count() > 0 {
+        play_sound_effect(&mut commands, &asset_server, &settings, &config.flap);
+    }
+    //the score sound is for passing a moai, scores that grow with distance or
+    //time would play it all the time
+    let scored = passed_events.read().count() > 0;
+    let high_score = score_events
+        .read()
+        .any(|event| matches!(event, ScoreEvent::NewHighScore));
+    if high_score {
+        play_sound_effect(&mut commands, &asset_server, &settings, &config.high_score);
+    } else if scored {
+        play_sound_effect(&mut commands, &asset_server, &settings, &config.score);
+    }
+    if death_events.read().count() > 0 {
+        play_sound_effect(&mut commands, &asset_server, &settings, &config.death);
+    }
+}
+
+fn switch_music(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    config_handle: Res<AudioConfigHandle>,
+    configs: Res<Assets<AudioConfig>>,
+    game_state: Res<State<GameState>>,
+    mut query_tracks: Query<&mut MusicTrack>,
+) {
+    let Some(config) = configs.get(&config_handle.0) else {
+        return;
+    };
+    let Some(path) = config.music.get(game_state.get()) else {
+        return;
+    };
+
+    let mut already_playing = false;
+    for mut track in query_tracks.iter_mut() {
+        if track.path == *path {
+            track.fading_out = false;
+            already_playing = true;
+        } else {
+            track.fading_out = true;
+        }
+    }
+    if already_playing {
+        return;
+    }
+
+    commands.spawn((
+        AudioBundle {
+            source: asset_server.load(path.to_string()),
+            settings: PlaybackSettings {
+                mode: PlaybackMode::Loop,
+                volume: Volume::new_relative(0.0),
+                ..default()
+            },
+        },
+        MusicTrack {
+            path: path.clone(),
+            volume: 0.0,
+            fading_out: false,
+        },
+        Name::new("MusicTrack"),
+    ));
+}
+
+//Crossfade is driven by the track's own volume, so it works the same way
+//when there is no audio device and sinks never get created.
+#[allow(clippy::too_many_arguments)]
+fn fade_music(
+    mut commands: Commands,
+    time: Res<Time>,
+    config_handle: Res<AudioConfigHandle>,
+    configs: Res<Assets<AudioConfig>>,
+    game_state: Res<State<GameState>>,
+    settings: Res<Settings>,
+    settings_origin: Res<SettingsOrigin>,
+    mut query_tracks: Query<(Entity, &mut MusicTrack, Option<&AudioSink>)>,
+) {
+    let Some(config) = configs.get(&config_handle.0) else {
+        return;
+    };
+    let step = if config.crossfade_seconds > 0.0 {
+        time.delta_seconds() / config.crossfade_seconds
+    } else {
+        1.0
+    };
+    let paused = match game_state.get() {
+        GameState::Pause => true,
+        GameState::Settings | GameState::Controls => settings_origin.0 == GameState::Pause,
+        _ => false,
+    };
+    let ducking = if paused { config.pause_volume } else { 1.0 };
+
+    for (entity, mut track, sink) in query_tracks.iter_mut() {
+        track.volume = if track.fading_out {
+            f32::max(track.volume - step, 0.0)
+        } else {
+            f32::min(track.volume + step, 1.0)
+        };
+
+        if track.fading_out && track.volume <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        if let Some(sink) = sink {
+            sink.set_volume(track.volume * ducking * settings.music_volume());
+        }
+    }
+}
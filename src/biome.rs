@@ -0,0 +1,327 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    consts, ron_asset::RonAssetLoader, state::gamestate::GameState, state::settings::Settings,
+    state::statistics::RunStatistics,
+};
+
+pub struct BiomePlugin;
+
+impl Plugin for BiomePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Biomes>()
+            .register_asset_loader(RonAssetLoader::<Biomes>::new(&["biomes.ron"]))
+            .init_resource::<ActiveBiome>()
+            .add_systems(Startup, (load_biomes, spawn_biome_transition_overlay))
+            .add_systems(Update, apply_loaded_biomes)
+            .add_systems(OnEnter(GameState::MainMenu), reset_biome)
+            .add_systems(OnExit(GameState::GameOver), reset_biome)
+            .add_systems(OnEnter(GameState::NameEntry), cancel_biome_transition)
+            .add_systems(OnEnter(GameState::GameOver), cancel_biome_transition)
+            .add_systems(
+                Update,
+                (
+                    start_biome_transition.run_if(in_state(GameState::Playing)),
+                    //also finishes in the pause menu and its screens, so it is
+                    //never left half faded
+                    update_biome_transition,
+                )
+                    .chain(),
+            );
+    }
+}
+
+//Biomes are described in a data file (see `consts::BIOMES_PATH`).
+//The world moves to the next biome every `points_per_biome` moai passed,
+//whatever the game mode scores, and stays in the last one once it is reached.
+#[derive(Debug, Asset, TypePath, Deserialize)]
+pub struct Biomes {
+    pub points_per_biome: u32,
+    pub biomes: Vec<Biome>,
+}
+
+impl Biomes {
+    fn index_for_moai_passed(&self, moai_passed: u32) -> usize {
+        if self.points_per_biome == 0 || self.biomes.is_empty() {
+            return 0;
+        }
+        usize::min(
+            (moai_passed / self.points_per_biome) as usize,
+            self.biomes.len() - 1,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Biome {
+    pub name: String,
+    pub background: String,
+    #[serde(default = "default_moai_tint")]
+    pub moai_tint: (f32, f32, f32),
+    #[serde(default)]
+    pub moai_head_texture: Option<String>,
+    #[serde(default)]
+    pub moai_body_texture: Option<String>,
+    #[serde(default = "default_gravity")]
+    pub gravity: f32,
+    //upward acceleration, counteracts gravity
+    #[serde(default)]
+    pub buoyancy: f32,
+    //fraction of velocity lost per second
+    #[serde(default)]
+    pub drag: f32,
+    #[serde(default = "default_flap_velocity")]
+    pub flap_velocity: f32,
+    #[serde(default = "default_scroll_speed")]
+    pub scroll_speed: f32,
+}
+
+fn default_moai_tint() -> (f32, f32, f32) {
+    (1.0, 1.0, 1.0)
+}
+
+fn default_gravity() -> f32 {
+    consts::G_FORCE_ACCELERATION
+}
+
+fn default_flap_velocity() -> f32 {
+    consts::PEBBLE_DEFAULT_VELOCITY
+}
+
+fn default_scroll_speed() -> f32 {
+    consts::MOAI_MOVE_SPEED
+}
+
+impl Default for Biome {
+    fn default() -> Self {
+        Biome {
+            name: "Meadow".to_string(),
+            background: consts::BACKGROUND_LAYERS_PATH.to_string(),
+            moai_tint: default_moai_tint(),
+            moai_head_texture: None,
+            moai_body_texture: None,
+            gravity: default_gravity(),
+            buoyancy: 0.0,
+            drag: 0.0,
+            flap_velocity: default_flap_velocity(),
+            scroll_speed: default_scroll_speed(),
+        }
+    }
+}
+
+impl Biome {
+    pub fn moai_color(&self) -> Color {
+        let (r, g, b) = self.moai_tint;
+        Color::rgb(r, g, b)
+    }
+
+    pub fn acceleration(&self) -> f32 {
+        self.gravity + self.buoyancy
+    }
+}
+
+#[derive(Debug, Resource, Default)]
+pub struct ActiveBiome {
+    index: usize,
+    biome: Biome,
+}
+
+impl ActiveBiome {
+    pub fn get(&self) -> &Biome {
+        &self.biome
+    }
+}
+
+#[derive(Resource)]
+struct BiomesHandle(Handle<Biomes>);
+
+const BIOME_TRANSITION_SECONDS: f32 = 1.2;
+const BIOME_TRANSITION_MAX_ALPHA: f32 = 0.85;
+
+#[derive(Debug, Component)]
+struct BiomeTransitionOverlay {
+    timer: Timer,
+    target: Option<usize>,
+}
+
+#[derive(Debug, Component)]
+struct BiomeTransitionLabel;
+
+fn load_biomes(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(BiomesHandle(asset_server.load(consts::BIOMES_PATH)));
+}
+
+fn apply_loaded_biomes(
+    mut asset_events: EventReader<AssetEvent<Biomes>>,
+    biomes_handle: Res<BiomesHandle>,
+    biomes: Res<Assets<Biomes>>,
+    mut active_biome: ResMut<ActiveBiome>,
+) {
+    let loaded = asset_events.read().fold(false, |loaded, event| {
+        loaded
+            || event.is_loaded_with_dependencies(&biomes_handle.0)
+            || event.is_modified(&biomes_handle.0)
+    });
+    if !loaded {
+        return;
+    }
+    let Some(biomes) = biomes.get(&biomes_handle.0) else {
+        return;
+    };
+    let index = usize::min(active_biome.index, biomes.biomes.len().saturating_sub(1));
+    if let Some(biome) = biomes.biomes.get(index) {
+        *active_biome = ActiveBiome {
+            index,
+            biome: biome.clone(),
+        };
+    }
+}
+
+fn reset_biome(
+    biomes_handle: Res<BiomesHandle>,
+    biomes: Res<Assets<Biomes>>,
+    mut active_biome: ResMut<ActiveBiome>,
+    query_overlay: Query<(&mut BiomeTransitionOverlay, &mut BackgroundColor)>,
+    query_label: Query<&mut Text, With<BiomeTransitionLabel>>,
+) {
+    let biome = biomes
+        .get(&biomes_handle.0)
+        .and_then(|biomes| biomes.biomes.first().cloned())
+        .unwrap_or_default();
+    *active_biome = ActiveBiome { index: 0, biome };
+    cancel_biome_transition(query_overlay, query_label);
+}
+
+//the run ended, the results are shown without the overlay
+fn cancel_biome_transition(
+    mut query_overlay: Query<(&mut BiomeTransitionOverlay, &mut BackgroundColor)>,
+    mut query_label: Query<&mut Text, With<BiomeTransitionLabel>>,
+) {
+    for (mut overlay, mut background_color) in query_overlay.iter_mut() {
+        overlay.target = None;
+        background_color.0.set_a(0.0);
+    }
+    for mut text in query_label.iter_mut() {
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(0.0);
+        }
+    }
+}
+
+fn spawn_biome_transition_overlay(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    height: Val::Percent(100.0),
+                    width: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::rgba(1.0, 1.0, 1.0, 0.0).into(),
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            BiomeTransitionOverlay {
+                timer: Timer::from_seconds(BIOME_TRANSITION_SECONDS, TimerMode::Once),
+                target: None,
+            },
+            Name::new("BiomeTransitionOverlay"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 50.0,
+                        color: Color::rgba(0.0, 0.0, 0.0, 0.0),
+                        ..default()
+                    },
+                ),
+                BiomeTransitionLabel,
+                Name::new("BiomeTransitionLabel"),
+            ));
+        });
+}
+
+fn start_biome_transition(
+    run_statistics: Res<RunStatistics>,
+    biomes_handle: Res<BiomesHandle>,
+    biomes: Res<Assets<Biomes>>,
+    active_biome: Res<ActiveBiome>,
+    mut query_overlay: Query<&mut BiomeTransitionOverlay>,
+    mut query_label: Query<&mut Text, With<BiomeTransitionLabel>>,
+) {
+    let Some(biomes) = biomes.get(&biomes_handle.0) else {
+        return;
+    };
+    let target = biomes.index_for_moai_passed(run_statistics.moai_passed());
+    if target == active_biome.index {
+        return;
+    }
+    let mut overlay = query_overlay.single_mut();
+    if overlay.target.is_some() {
+        return;
+    }
+    overlay.target = Some(target);
+    overlay.timer.reset();
+
+    if let Ok(mut text) = query_label.get_single_mut() {
+        let section = text.sections.first_mut().expect("to have a TextSection");
+        section.value = format!("Entering {}", biomes.biomes[target].name);
+    }
+}
+
+//The screen fades out and back in; the biome itself is switched at the darkest point,
+//so that the background swap is hidden behind the overlay.
+//With reduced motion only the biome name is shown.
+fn update_biome_transition(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    biomes_handle: Res<BiomesHandle>,
+    biomes: Res<Assets<Biomes>>,
+    mut active_biome: ResMut<ActiveBiome>,
+    mut query_overlay: Query<(&mut BiomeTransitionOverlay, &mut BackgroundColor)>,
+    mut query_label: Query<&mut Text, With<BiomeTransitionLabel>>,
+) {
+    let (mut overlay, mut background_color) = query_overlay.single_mut();
+    let Some(target) = overlay.target else {
+        return;
+    };
+
+    overlay.timer.tick(time.delta());
+    let progress = overlay.timer.percent();
+
+    if progress >= 0.5 && active_biome.index != target {
+        if let Some(biome) = biomes
+            .get(&biomes_handle.0)
+            .and_then(|biomes| biomes.biomes.get(target))
+        {
+            *active_biome = ActiveBiome {
+                index: target,
+                biome: biome.clone(),
+            };
+        }
+    }
+
+    let alpha = if overlay.timer.finished() {
+        overlay.target = None;
+        0.0
+    } else {
+        1.0 - (2.0 * progress - 1.0).abs()
+    };
+    let overlay_alpha = if settings.reduced_motion {
+        0.0
+    } else {
+        alpha * BIOME_TRANSITION_MAX_ALPHA
+    };
+    background_color.0.set_a(overlay_alpha);
+    if let Ok(mut text) = query_label.get_single_mut() {
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(alpha);
+        }
+    }
+}
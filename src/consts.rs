@@ -5,6 +5,9 @@ pub const PEBBLE_WIDTH: f32 = 90.0;
 pub const PEBBLE_HEIGHT: f32 = 52.0;
 pub const PEBBLE_START_Y_RANGE: std::ops::Range<f32> = -300.0..300.0;
 pub const PEBBLE_DEFAULT_VELOCITY: f32 = 400.0;
+//while waiting for the first flap
+pub const PEBBLE_BOB_HEIGHT: f32 = 15.0;
+pub const PEBBLE_BOB_SPEED: f32 = 4.0;
 pub const G_FORCE_ACCELERATION: f32 = -400.0;
 pub const MOAI_WIDTH: f32 = 100.0;
 pub const MOAI_HEIGHT: f32 = 197.2;
@@ -20,5 +23,33 @@ pub const MOAI_HORIZONTAL_DISTANCE: f32 = 800.0;
 pub const MOAI_VERTICAL_DISTANCE: f32 = 300.0;
 pub const MOAI_HEIGHT_RANGE: std::ops::Range<f32> = -200.0..200.0;
 pub const MOAI_MOVE_SPEED: f32 = 200.0;
+//for scoring by distance
+pub const PIXELS_PER_METRE: f32 = 100.0;
+//clearance under which passing a moai counts as a near miss
+pub const NEAR_MISS_DISTANCE: f32 = 15.0;
+//bonus scoring, see `state::bonus_scoring`
+pub const NEAR_MISS_BONUS: u32 = 3;
+pub const CENTRE_BONUS: u32 = 1;
+//accuracy needed for a pass through the centre band of the gap
+pub const CENTRE_BAND_ACCURACY: f32 = 0.8;
+pub const MAX_COMBO_MULTIPLIER: u32 = 5;
 
-pub const HIGHSCORE_PATH: &str = "highscore";
+pub const SAVE_PATH: &str = "save";
+//raw u32 highscore written by older versions, migrated on startup
+pub const LEGACY_HIGHSCORE_PATH: &str = "highscore";
+
+pub const LEADERBOARD_SIZE: usize = 10;
+pub const DEFAULT_PLAYER_NAME: &str = "Player";
+pub const MAX_PLAYER_NAME_LENGTH: usize = 12;
+
+pub const BACKGROUND_LAYERS_PATH: &str = "backgrounds/default.layers.ron";
+pub const BIOMES_PATH: &str = "default.biomes.ron";
+pub const ACHIEVEMENTS_PATH: &str = "default.achievements.ron";
+pub const MEDALS_PATH: &str = "default.medals.ron";
+pub const AUDIO_CONFIG_PATH: &str = "audio/default.audio.ron";
+pub const SETTINGS_PATH: &str = "settings";
+
+//folder in the platform data directory for saves and settings
+pub const STORAGE_DIR_NAME: &str = "flappy-pebble";
+//where older versions kept saves and settings
+pub const LEGACY_STORAGE_DIR: &str = "assets";
@@ -5,6 +5,7 @@ use bevy::render::camera::ScalingMode;
 use bevy::window::WindowResized;
 
 use crate::consts;
+use crate::state::settings::Difficulty;
 
 #[derive(Debug, Resource, Clone, Copy, Default)]
 pub struct GameSize {
@@ -97,7 +98,8 @@ fn get_minimal_logical_height() -> f32 {
     let moai_height_range_start = get_bound_or(consts::MOAI_HEIGHT_RANGE.start_bound(), 0.0);
     consts::MOAI_HEIGHT * 2.0
         + (moai_height_range_end - moai_height_range_start)
-        + consts::MOAI_VERTICAL_DISTANCE
+        //the widest gap
+        + Difficulty::Easy.moai_gap()
 }
 
 fn calculate_scale(minimal_logical: f32, physical: f32) -> f32 {
@@ -0,0 +1,116 @@
+use bevy::core::FrameCount;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use super::action_map::{Action, ActionMap, Binding, BindingCapture};
+use super::context::resolve_action;
+use super::gamepad::gamepad_buttons_just_pressed;
+use super::touch::{swipe_just_finished, touch_just_pressed};
+use crate::state::gamestate::GameState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputSource {
+    Keyboard,
+    Mouse,
+    Gamepad(Gamepad),
+    Touch,
+}
+
+#[derive(Debug, Event, Clone, Copy)]
+pub struct ActionEvent {
+    pub action: Action,
+    //tells prompts and rumble which device the player is holding
+    pub source: InputSource,
+    frame: u32,
+}
+
+//Set while a text field has the keyboard. Key presses then type text and are
+//not sent as actions, so e.g. typing an F does not toggle fullscreen.
+#[derive(Debug, Resource, Default)]
+pub struct TextInput(pub bool);
+
+//Every device is translated here, so supporting a new one only needs
+//another `Binding` variant and the presses that produce it.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn send_action_events(
+    action_map: Res<ActionMap>,
+    capture: Res<BindingCapture>,
+    text_input: Res<TextInput>,
+    game_state: Res<State<GameState>>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    gamepad: Res<Input<GamepadButton>>,
+    touches: Res<Touches>,
+    frame: Res<FrameCount>,
+    query_interactions: Query<&Interaction, Changed<Interaction>>,
+    mut action_events: EventWriter<ActionEvent>,
+) {
+    if capture.0.is_some() || capture.is_changed() {
+        return;
+    }
+
+    let keys = keyboard
+        .get_just_pressed()
+        .filter(|_| !text_input.0)
+        .map(|key| (Binding::Key(*key), InputSource::Keyboard));
+    let mouse_buttons = mouse
+        .get_just_pressed()
+        .map(|button| (Binding::Mouse(*button), InputSource::Mouse));
+    let gamepad_buttons = gamepad_buttons_just_pressed(&gamepad).map(|button| {
+        (
+            Binding::Gamepad(button.button_type),
+            InputSource::Gamepad(button.gamepad),
+        )
+    });
+    let touch = touch_just_pressed(&touches).then_some((Binding::Touch, InputSource::Touch));
+    let swipe = swipe_just_finished(&touches).then_some((Binding::Swipe, InputSource::Touch));
+
+    //a click or touch on a button belongs to the button only
+    let pointer_on_button = query_interactions
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed);
+
+    for (binding, source) in keys
+        .chain(mouse_buttons)
+        .chain(gamepad_buttons)
+        .chain(touch)
+        .chain(swipe)
+    {
+        if pointer_on_button && matches!(binding, Binding::Mouse(_) | Binding::Touch) {
+            continue;
+        }
+        if let Some(action) = resolve_action(*game_state.get(), &action_map, binding) {
+            action_events.send(ActionEvent {
+                action,
+                source,
+                frame: frame.0,
+            });
+        }
+    }
+}
+
+//Only events sent this frame are looked at, so systems that did not run for
+//a while (e.g. because of their state) never react to an old press. Events
+//are kept until FixedUpdate ran, which can take several frames, so the frame
+//they were sent in is compared too.
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    events: Res<'w, Events<ActionEvent>>,
+    frame: Res<'w, FrameCount>,
+}
+
+impl ActionInput<'_> {
+    pub fn read(&self) -> impl Iterator<Item = &ActionEvent> {
+        self.events
+            .iter_current_update_events()
+            .filter(|event| event.frame == self.frame.0)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.read().any(|event| event.action == action)
+    }
+}
+
+pub fn action_just_pressed(action: Action) -> impl FnMut(ActionInput) -> bool + Clone {
+    move |input: ActionInput| input.just_pressed(action)
+}
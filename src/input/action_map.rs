@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::context::active_together;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Action {
+    Flap,
+    Pause,
+    Confirm,
+    Back,
+    ToggleFullscreen,
+    NavigateUp,
+    NavigateDown,
+    NavigateLeft,
+    NavigateRight,
+}
+
+impl Action {
+    pub fn all() -> [Action; 9] {
+        [
+            Action::Flap,
+            Action::Pause,
+            Action::Confirm,
+            Action::Back,
+            Action::ToggleFullscreen,
+            Action::NavigateUp,
+            Action::NavigateDown,
+            Action::NavigateLeft,
+            Action::NavigateRight,
+        ]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::Flap => "Flap",
+            Action::Pause => "Pause",
+            Action::Confirm => "Confirm",
+            Action::Back => "Back",
+            Action::ToggleFullscreen => "Fullscreen",
+            Action::NavigateUp => "Navigate up",
+            Action::NavigateDown => "Navigate down",
+            Action::NavigateLeft => "Navigate left",
+            Action::NavigateRight => "Navigate right",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButtonType),
+    Touch,
+    Swipe,
+}
+
+impl std::fmt::Display for Binding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Binding::Key(key) => write!(f, "{key:?}"),
+            Binding::Mouse(button) => write!(f, "Mouse {button:?}"),
+            Binding::Gamepad(button) => write!(f, "Pad {button:?}"),
+            Binding::Touch => write!(f, "Touch"),
+            Binding::Swipe => write!(f, "Swipe right"),
+        }
+    }
+}
+
+#[derive(Debug, Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "StoredActionMap")]
+pub struct ActionMap(BTreeMap<Action, Vec<Binding>>);
+
+//Actions missing from stored settings (e.g. added in a later version) keep
+//their default bindings. Cleared actions are stored as an empty list.
+#[derive(Deserialize)]
+#[serde(rename = "ActionMap")]
+struct StoredActionMap(BTreeMap<Action, Vec<Binding>>);
+
+impl From<StoredActionMap> for ActionMap {
+    fn from(stored: StoredActionMap) -> Self {
+        let mut action_map = ActionMap::default();
+        action_map.0.extend(stored.0);
+        action_map
+    }
+}
+
+impl Default for ActionMap {
+    fn default() -> Self {
+        ActionMap(BTreeMap::from([
+            (
+                Action::Flap,
+                vec![
+                    Binding::Mouse(MouseButton::Left),
+                    Binding::Key(KeyCode::Space),
+                    Binding::Gamepad(GamepadButtonType::South),
+                    Binding::Touch,
+                ],
+            ),
+            (
+                Action::Pause,
+                vec![
+                    Binding::Key(KeyCode::Escape),
+                    Binding::Gamepad(GamepadButtonType::East),
+                ],
+            ),
+            (
+                Action::Confirm,
+                vec![
+                    Binding::Key(KeyCode::Return),
+                    Binding::Key(KeyCode::Space),
+                    Binding::Gamepad(GamepadButtonType::South),
+                ],
+            ),
+            (
+                Action::Back,
+                vec![
+                    Binding::Key(KeyCode::Escape),
+                    Binding::Gamepad(GamepadButtonType::East),
+                    Binding::Swipe,
+                ],
+            ),
+            (
+                Action::ToggleFullscreen,
+                vec![
+                    Binding::Key(KeyCode::F),
+                    Binding::Gamepad(GamepadButtonType::North),
+                ],
+            ),
+            (
+                Action::NavigateUp,
+                vec![
+                    Binding::Key(KeyCode::Up),
+                    Binding::Key(KeyCode::W),
+                    Binding::Gamepad(GamepadButtonType::DPadUp),
+                ],
+            ),
+            (
+                Action::NavigateDown,
+                vec![
+                    Binding::Key(KeyCode::Down),
+                    Binding::Key(KeyCode::S),
+                    Binding::Gamepad(GamepadButtonType::DPadDown),
+                ],
+            ),
+            (
+                Action::NavigateLeft,
+                vec![
+                    Binding::Key(KeyCode::Left),
+                    Binding::Key(KeyCode::A),
+                    Binding::Gamepad(GamepadButtonType::DPadLeft),
+                ],
+            ),
+            (
+                Action::NavigateRight,
+                vec![
+                    Binding::Key(KeyCode::Right),
+                    Binding::Key(KeyCode::D),
+                    Binding::Gamepad(GamepadButtonType::DPadRight),
+                ],
+            ),
+        ]))
+    }
+}
+
+impl ActionMap {
+    pub fn bindings(&self, action: Action) -> &[Binding] {
+        self.0.get(&action).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn add_binding(&mut self, action: Action, binding: Binding) {
+        let bindings = self.0.entry(action).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    pub fn clear_bindings(&mut self, action: Action) {
+        self.0.insert(action, Vec::new());
+    }
+
+    //other actions listened to in the same state that use the binding too
+    pub fn conflicts(&self, action: Action, binding: Binding) -> Vec<Action> {
+        self.0
+            .iter()
+            .filter(|(other, bindings)| {
+                **other != action && bindings.contains(&binding) && active_together(action, **other)
+            })
+            .map(|(other, _)| *other)
+            .collect()
+    }
+}
+
+//Set while the controls screen waits for a new binding. No actions are sent
+//while waiting and in the frame the binding is captured, so the pressed key
+//or button is not treated as an action at the same time.
+#[derive(Debug, Resource, Default)]
+pub struct BindingCapture(pub Option<Action>);
@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+
+use super::action_map::{Action, ActionMap, Binding};
+use crate::state::gamestate::GameState;
+
+//Groups of actions that are only listened to in some states. When a binding
+//is shared, the context with the highest priority handles it and the others
+//never see the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputContext {
+    Gameplay,
+    Menu,
+    Global,
+}
+
+impl InputContext {
+    //highest priority first
+    pub const ALL: [InputContext; 3] = [
+        InputContext::Gameplay,
+        InputContext::Menu,
+        InputContext::Global,
+    ];
+
+    //The context of the screen shown in the state, `Global` when there is
+    //none. Every state is listed, so a new one can't silently get no input.
+    fn of_screen(state: GameState) -> InputContext {
+        match state {
+            GameState::Playing => InputContext::Gameplay,
+            GameState::MainMenu
+            | GameState::NameEntry
+            | GameState::GameOver
+            | GameState::Pause
+            | GameState::Settings
+            | GameState::Controls
+            | GameState::Leaderboard
+            | GameState::Statistics
+            | GameState::Achievements => InputContext::Menu,
+            GameState::Exit => InputContext::Global,
+        }
+    }
+
+    //the context of the screen and the global one
+    pub fn is_active(self, state: GameState) -> bool {
+        self.is_active_on(InputContext::of_screen(state))
+    }
+
+    fn is_active_on(self, screen: InputContext) -> bool {
+        self == screen || self == InputContext::Global
+    }
+
+    //inside a context the earlier action wins
+    pub fn actions(self) -> &'static [Action] {
+        match self {
+            InputContext::Gameplay => &[Action::Pause, Action::Flap],
+            InputContext::Menu => &[
+                Action::Back,
+                Action::Confirm,
+                Action::NavigateUp,
+                Action::NavigateDown,
+                Action::NavigateLeft,
+                Action::NavigateRight,
+            ],
+            InputContext::Global => &[Action::ToggleFullscreen],
+        }
+    }
+}
+
+//actions that can be triggered on a screen of the context, in the order they
+//are handled
+fn active_actions(screen: InputContext) -> impl Iterator<Item = Action> {
+    InputContext::ALL
+        .into_iter()
+        .filter(move |context| context.is_active_on(screen))
+        .flat_map(|context| context.actions().iter().copied())
+}
+
+//the single action the binding triggers in the state, if any
+pub fn resolve_action(
+    state: GameState,
+    action_map: &ActionMap,
+    binding: Binding,
+) -> Option<Action> {
+    active_actions(InputContext::of_screen(state))
+        .find(|action| action_map.bindings(*action).contains(&binding))
+}
+
+//whether there is a screen on which both actions can be triggered
+pub fn active_together(action: Action, other: Action) -> bool {
+    InputContext::ALL.into_iter().any(|screen| {
+        let actions: Vec<_> = active_actions(screen).collect();
+        actions.contains(&action) && actions.contains(&other)
+    })
+}
+
+pub(super) fn report_overlapping_bindings(action_map: Res<ActionMap>) {
+    for screen in InputContext::ALL {
+        let actions: Vec<_> = active_actions(screen).collect();
+        for (index, action) in actions.iter().enumerate() {
+            for binding in action_map.bindings(*action) {
+                let shadowed: Vec<_> = actions[index + 1..]
+                    .iter()
+                    .filter(|other| action_map.bindings(**other).contains(binding))
+                    .map(|other| other.label())
+                    .collect();
+                if !shadowed.is_empty() {
+                    warn!(
+                        "On {screen:?} screens, {binding} triggers {} and never reaches {}",
+                        action.label(),
+                        shadowed.join(", ")
+                    );
+                }
+            }
+        }
+    }
+}
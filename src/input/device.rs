@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+use super::action_event::{ActionInput, InputSource};
+
+//The device that produced the most recent press, so prompts can show
+//glyphs for what the player is holding.
+#[derive(Debug, Resource, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LastInputDevice {
+    #[default]
+    KeyboardMouse,
+    Gamepad(Gamepad),
+    Touch,
+}
+
+impl LastInputDevice {
+    //the gamepad the player is currently using, if any
+    pub fn gamepad(self) -> Option<Gamepad> {
+        match self {
+            LastInputDevice::Gamepad(gamepad) => Some(gamepad),
+            _ => None,
+        }
+    }
+}
+
+impl From<InputSource> for LastInputDevice {
+    fn from(source: InputSource) -> Self {
+        match source {
+            InputSource::Keyboard | InputSource::Mouse => LastInputDevice::KeyboardMouse,
+            InputSource::Gamepad(gamepad) => LastInputDevice::Gamepad(gamepad),
+            InputSource::Touch => LastInputDevice::Touch,
+        }
+    }
+}
+
+//follows the action events, so only presses the action map knows about count
+pub(super) fn track_last_input_device(
+    action_input: ActionInput,
+    mut last_device: ResMut<LastInputDevice>,
+) {
+    if let Some(event) = action_input.read().last() {
+        last_device.set_if_neq(event.source.into());
+    }
+}
@@ -0,0 +1,30 @@
+use bevy::input::gamepad::GamepadConnectionEvent;
+use bevy::prelude::*;
+
+use super::device::LastInputDevice;
+
+//buttons pressed this frame, on any gamepad
+pub fn gamepad_buttons_just_pressed(
+    gamepad_input: &Input<GamepadButton>,
+) -> impl Iterator<Item = GamepadButton> + '_ {
+    gamepad_input.get_just_pressed().copied()
+}
+
+//The gamepad the player was using got unplugged. Other gamepads coming and
+//going don't interrupt anything.
+#[derive(Debug, Event)]
+pub struct ActiveGamepadDisconnected(pub Gamepad);
+
+//an unplugged gamepad can't be the active device anymore
+pub(super) fn forget_disconnected_gamepad(
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    mut last_device: ResMut<LastInputDevice>,
+    mut disconnected_events: EventWriter<ActiveGamepadDisconnected>,
+) {
+    for event in connection_events.read() {
+        if event.disconnected() && *last_device == LastInputDevice::Gamepad(event.gamepad) {
+            *last_device = LastInputDevice::KeyboardMouse;
+            disconnected_events.send(ActiveGamepadDisconnected(event.gamepad));
+        }
+    }
+}
@@ -0,0 +1,61 @@
+mod action_event;
+mod action_map;
+mod context;
+mod device;
+mod gamepad;
+mod touch;
+
+pub use action_event::{action_just_pressed, ActionEvent, ActionInput, InputSource, TextInput};
+pub use action_map::{Action, ActionMap, Binding, BindingCapture};
+pub use context::InputContext;
+pub use device::LastInputDevice;
+pub use gamepad::ActiveGamepadDisconnected;
+pub use touch::TouchControls;
+
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+use crate::state::settings::Settings;
+
+pub struct ActionInputPlugin;
+
+impl Plugin for ActionInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionMap>()
+            .init_resource::<BindingCapture>()
+            .init_resource::<TextInput>()
+            .init_resource::<TouchControls>()
+            .init_resource::<LastInputDevice>()
+            .add_event::<ActionEvent>()
+            .add_event::<ActiveGamepadDisconnected>()
+            .add_systems(
+                PreUpdate,
+                (
+                    apply_bindings.run_if(resource_changed::<Settings>()),
+                    action_event::send_action_events.after(UiSystem::Focus),
+                    touch::detect_touch_controls,
+                    device::track_last_input_device,
+                    gamepad::forget_disconnected_gamepad,
+                )
+                    .chain()
+                    .after(InputSystem),
+            );
+
+        if cfg!(debug_assertions) {
+            app.add_systems(
+                PreUpdate,
+                context::report_overlapping_bindings
+                    .run_if(resource_changed::<ActionMap>())
+                    .after(apply_bindings),
+            );
+        }
+    }
+}
+
+//bindings are stored with the rest of the settings and applied from there
+fn apply_bindings(settings: Res<Settings>, mut action_map: ResMut<ActionMap>) {
+    if *action_map != settings.controls {
+        *action_map = settings.controls.clone();
+    }
+}
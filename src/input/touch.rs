@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+//horizontal distance in logical pixels a finger has to travel for a swipe
+const SWIPE_DISTANCE: f32 = 120.0;
+
+//any finger counts, the screen is one big button
+pub fn touch_just_pressed(touches: &Touches) -> bool {
+    touches.any_just_pressed()
+}
+
+//a mostly horizontal left-to-right swipe, like the back gesture on phones
+pub fn swipe_just_finished(touches: &Touches) -> bool {
+    touches.iter_just_released().any(|touch| {
+        let distance = touch.distance();
+        distance.x > SWIPE_DISTANCE && distance.x > 2.0 * distance.y.abs()
+    })
+}
+
+//On-screen controls are only shown once a touch was seen, and hidden again
+//when the keyboard is used.
+#[derive(Debug, Resource, Default)]
+pub struct TouchControls {
+    pub visible: bool,
+}
+
+pub(super) fn detect_touch_controls(
+    touches: Res<Touches>,
+    keyboard: Res<Input<KeyCode>>,
+    mut touch_controls: ResMut<TouchControls>,
+) {
+    let visible = if touches.any_just_pressed() {
+        true
+    } else if keyboard.get_just_pressed().next().is_some() {
+        false
+    } else {
+        return;
+    };
+    if touch_controls.visible != visible {
+        touch_controls.visible = visible;
+    }
+}
@@ -1,9 +1,13 @@
+mod audio;
+mod biome;
 mod consts;
 mod game_size;
-mod gamepad_util;
+mod input;
+mod ron_asset;
+mod rumble;
 mod screen_entity;
 mod state;
-mod touch_util;
+mod storage;
 mod ui;
 
 use bevy::prelude::*;
@@ -44,10 +48,15 @@ fn main() {
             )),
             ..default()
         }),
+        storage::StoragePlugin,
         state::StatePlugin,
         screen_entity::GameEntityPlugin,
         ui::UiPlugin,
         game_size::GameSizePlugin::new(0.0, 0.0),
+        biome::BiomePlugin,
+        audio::GameAudioPlugin,
+        input::ActionInputPlugin,
+        rumble::RumblePlugin,
     ));
 
     if cfg!(feature = "egui") {
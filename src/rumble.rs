@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use bevy::input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy::prelude::*;
+
+use crate::{
+    input::{InputSource, LastInputDevice},
+    screen_entity::{MoaiPassed, PebbleDied, PebbleFlapped},
+    state::settings::Settings,
+};
+
+pub struct RumblePlugin;
+
+impl Plugin for RumblePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, rumble);
+    }
+}
+
+//strong motor for crashes, weak motor for the light feedback
+fn rumble_request(
+    gamepad: Gamepad,
+    strong: f32,
+    weak: f32,
+    milliseconds: u64,
+) -> GamepadRumbleRequest {
+    GamepadRumbleRequest::Add {
+        duration: Duration::from_millis(milliseconds),
+        intensity: GamepadRumbleIntensity {
+            strong_motor: strong,
+            weak_motor: weak,
+        },
+        gamepad,
+    }
+}
+
+//A flap rumbles the gamepad it was pressed on, everything else the gamepad
+//that produced the last input. Nothing shakes while playing with the
+//keyboard.
+fn rumble(
+    settings: Res<Settings>,
+    last_device: Res<LastInputDevice>,
+    mut flap_events: EventReader<PebbleFlapped>,
+    mut passed_events: EventReader<MoaiPassed>,
+    mut death_events: EventReader<PebbleDied>,
+    mut rumble_requests: EventWriter<GamepadRumbleRequest>,
+) {
+    let flap_gamepad = flap_events
+        .read()
+        .filter_map(|flap| match flap.source {
+            InputSource::Gamepad(gamepad) => Some(gamepad),
+            _ => None,
+        })
+        .last();
+    let scored = passed_events.read().count() > 0;
+    let died = death_events.read().count() > 0;
+    if !settings.rumble {
+        return;
+    }
+
+    let request = if died && settings.death_rumble > 0.0 {
+        last_device.gamepad().map(|gamepad| {
+            rumble_request(gamepad, settings.death_rumble, settings.death_rumble, 400)
+        })
+    } else if scored && settings.score_rumble > 0.0 {
+        last_device
+            .gamepad()
+            .map(|gamepad| rumble_request(gamepad, 0.0, settings.score_rumble, 120))
+    } else if settings.flap_rumble > 0.0 {
+        flap_gamepad.map(|gamepad| rumble_request(gamepad, 0.0, settings.flap_rumble, 50))
+    } else {
+        None
+    };
+    if let Some(request) = request {
+        rumble_requests.send(request);
+    }
+}
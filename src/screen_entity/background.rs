@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    biome::ActiveBiome, game_size::GameSize, ron_asset::RonAssetLoader,
+    state::play_phase::GameplaySet, state::settings::Settings,
+};
+
+pub struct BackgroundPlugin;
+
+impl Plugin for BackgroundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<BackgroundLayers>()
+            .register_asset_loader(RonAssetLoader::<BackgroundLayers>::new(&["layers.ron"]))
+            .register_type::<ParallaxLayer>()
+            .add_systems(Startup, load_background)
+            .add_systems(
+                Update,
+                (
+                    follow_active_biome.run_if(resource_changed::<ActiveBiome>()),
+                    spawn_background,
+                    render_background,
+                )
+                    .chain(),
+            )
+            .add_systems(FixedUpdate, scroll_background.in_set(GameplaySet));
+    }
+}
+
+//Layers are described in a data file referenced by the active biome
+//(see `consts::BACKGROUND_LAYERS_PATH` for the default one),
+//so new layers can be added without touching the code.
+#[derive(Debug, Asset, TypePath, Deserialize)]
+pub struct BackgroundLayers {
+    pub layers: Vec<BackgroundLayer>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BackgroundLayer {
+    pub name: String,
+    pub texture: String,
+    //fraction of the biome's scroll speed
+    pub speed: f32,
+    pub depth: f32,
+    pub size: (f32, f32),
+    #[serde(default)]
+    pub anchor: LayerAnchor,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub enum LayerAnchor {
+    //stretch tiles to the whole game height
+    #[default]
+    Fill,
+    Top(f32),
+    Center(f32),
+    Bottom(f32),
+}
+
+#[derive(Resource)]
+struct Background {
+    layers: Handle<BackgroundLayers>,
+}
+
+#[derive(Debug, Component, Reflect)]
+struct ParallaxLayer {
+    speed: f32,
+    tile_width: f32,
+    offset: f32,
+}
+
+#[derive(Debug, Component)]
+struct ParallaxTile {
+    index: usize,
+}
+
+fn load_background(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    active_biome: Res<ActiveBiome>,
+) {
+    commands.insert_resource(Background {
+        layers: asset_server.load(&active_biome.get().background),
+    });
+}
+
+fn follow_active_biome(
+    active_biome: Res<ActiveBiome>,
+    asset_server: Res<AssetServer>,
+    mut background: ResMut<Background>,
+) {
+    let layers = asset_server.load(&active_biome.get().background);
+    if background.layers != layers {
+        background.layers = layers;
+    }
+}
+
+//Tiles are respawned whenever the data file is (re)loaded or the game size changes,
+//so that there are always enough of them to cover the whole screen.
+fn spawn_background(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<BackgroundLayers>>,
+    background: Res<Background>,
+    background_layers: Res<Assets<BackgroundLayers>>,
+    asset_server: Res<AssetServer>,
+    game_size: Res<GameSize>,
+    query_layers: Query<Entity, With<ParallaxLayer>>,
+) {
+    let layers_changed = asset_events.read().fold(false, |changed, event| {
+        changed
+            || event.is_loaded_with_dependencies(&background.layers)
+            || event.is_modified(&background.layers)
+    });
+    if !layers_changed && !game_size.is_changed() && !background.is_changed() {
+        return;
+    }
+    let Some(background_layers) = background_layers.get(&background.layers) else {
+        return;
+    };
+
+    for layer in query_layers.iter() {
+        commands.entity(layer).despawn_recursive();
+    }
+
+    let game_width = game_size.max_x - game_size.min_x;
+    let game_height = game_size.max_y - game_size.min_y;
+    for layer in background_layers.layers.iter() {
+        let (tile_width, tile_height) = layer.size;
+        if tile_width <= 0.0 {
+            warn!("Background layer {} has non-positive width", layer.name);
+            continue;
+        }
+        let (tile_height, y) = match layer.anchor {
+            LayerAnchor::Fill => (game_height, 0.0),
+            LayerAnchor::Top(offset) => (tile_height, game_size.max_y - tile_height / 2.0 - offset),
+            LayerAnchor::Center(offset) => (tile_height, offset),
+            LayerAnchor::Bottom(offset) => {
+                (tile_height, game_size.min_y + tile_height / 2.0 + offset)
+            }
+        };
+        //one extra tile to cover the gap while the layer is shifted
+        let tiles_count = (game_width / tile_width).ceil() as usize + 1;
+        let texture: Handle<Image> = asset_server.load(&layer.texture);
+
+        commands
+            .spawn((
+                SpatialBundle::from_transform(Transform::from_xyz(0.0, y, layer.depth)),
+                ParallaxLayer {
+                    speed: layer.speed,
+                    tile_width,
+                    offset: 0.0,
+                },
+                Name::new(format!("BackgroundLayer{}", layer.name)),
+            ))
+            .with_children(|parent| {
+                for index in 0..tiles_count {
+                    parent.spawn((
+                        SpriteBundle {
+                            texture: texture.clone(),
+                            sprite: Sprite {
+                                custom_size: Some(Vec2 {
+                                    x: tile_width,
+                                    y: tile_height,
+                                }),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        ParallaxTile { index },
+                    ));
+                }
+            });
+    }
+}
+
+fn scroll_background(
+    time: Res<Time<Fixed>>,
+    active_biome: Res<ActiveBiome>,
+    settings: Res<Settings>,
+    mut query_layers: Query<&mut ParallaxLayer>,
+) {
+    if settings.reduced_motion {
+        return;
+    }
+    let scroll_speed = active_biome.get().scroll_speed;
+    for mut layer in query_layers.iter_mut() {
+        let offset = layer.offset + layer.speed * scroll_speed * time.delta_seconds();
+        layer.offset = offset.rem_euclid(layer.tile_width);
+    }
+}
+
+fn render_background(
+    game_size: Res<GameSize>,
+    query_layers: Query<(&ParallaxLayer, &Children)>,
+    mut query_tiles: Query<(&ParallaxTile, &mut Transform)>,
+) {
+    for (layer, children) in query_layers.iter() {
+        for &child in children.iter() {
+            if let Ok((tile, mut transform)) = query_tiles.get_mut(child) {
+                transform.translation.x =
+                    game_size.min_x + layer.tile_width * (tile.index as f32 + 0.5) - layer.offset;
+            }
+        }
+    }
+}
@@ -1,8 +1,14 @@
 use bevy::prelude::*;
 use rand::Rng;
 
+use super::pebble::Pebble;
 use crate::{
-    consts, game_size::GameSize, state::gamescore::GameScore, state::gamestate::GameState,
+    biome::ActiveBiome,
+    consts,
+    game_size::GameSize,
+    state::gamestate::GameState,
+    state::play_phase::GameplaySet,
+    state::run_info::{setup_run, RunInfo},
 };
 
 pub struct MoaiPlugin;
@@ -10,20 +16,25 @@ pub struct MoaiPlugin;
 impl Plugin for MoaiPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Moai>()
+            .add_event::<MoaiPassed>()
             .add_systems(Startup, load_texture)
+            .add_systems(
+                Update,
+                update_moai_texture.run_if(resource_changed::<ActiveBiome>()),
+            )
             .add_systems(
                 OnTransition {
                     from: GameState::MainMenu,
                     to: GameState::Playing,
                 },
-                (despawn_all_moai, spawn_init_moai).chain(),
+                (despawn_all_moai, spawn_init_moai).chain().after(setup_run),
             )
             .add_systems(
                 OnTransition {
                     from: GameState::GameOver,
                     to: GameState::Playing,
                 },
-                (despawn_all_moai, spawn_init_moai).chain(),
+                (despawn_all_moai, spawn_init_moai).chain().after(setup_run),
             )
             .add_systems(OnEnter(GameState::MainMenu), despawn_all_moai)
             .add_systems(Update, render_moai.run_if(in_state(GameState::Playing)))
@@ -31,23 +42,38 @@ impl Plugin for MoaiPlugin {
                 Update,
                 (despawn_moai_outside_screen).run_if(in_state(GameState::Playing)),
             )
-            .add_systems(FixedUpdate, move_moai.run_if(in_state(GameState::Playing)));
+            .add_systems(
+                FixedUpdate,
+                (move_moai, pass_moai).chain().in_set(GameplaySet),
+            );
     }
 }
 
+//sent when the middle of a moai goes past the pebble, see `state::scoring_rule`
+#[derive(Debug, Event)]
+pub struct MoaiPassed;
+
 #[derive(Debug, Clone, Copy, Component, Reflect)]
 pub struct Moai {
     pub x: f32,
     pub height: f32,
+    pub gap: f32,
     pub passed: bool,
+    //smallest vertical space left between the pebble and the moai while
+    //they overlapped, see `pebble::check_collisions`
+    pub clearance: f32,
+    pub cleared: bool,
 }
 
 impl Moai {
-    fn new(x: f32, height: f32) -> Self {
+    fn new(x: f32, height: f32, gap: f32) -> Self {
         Self {
             x,
             height,
+            gap,
             passed: false,
+            clearance: f32::MAX,
+            cleared: false,
         }
     }
 }
@@ -56,11 +82,18 @@ impl Moai {
 struct MoaiTexture {
     head: Handle<Image>,
     body: Handle<Image>,
+    default_head: Handle<Image>,
+    default_body: Handle<Image>,
 }
 
 impl MoaiTexture {
     fn new(head: Handle<Image>, body: Handle<Image>) -> MoaiTexture {
-        MoaiTexture { head, body }
+        MoaiTexture {
+            head: head.clone(),
+            body: body.clone(),
+            default_head: head,
+            default_body: body,
+        }
     }
 }
 
@@ -70,11 +103,29 @@ fn load_texture(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.insert_resource(MoaiTexture::new(head, body));
 }
 
-fn spawn_moai_body_sprites(parent: &mut ChildBuilder, texture: Handle<Image>) {
+//Biomes may replace moai textures, otherwise the default ones are used.
+fn update_moai_texture(
+    active_biome: Res<ActiveBiome>,
+    asset_server: Res<AssetServer>,
+    mut moai_texture: ResMut<MoaiTexture>,
+) {
+    let biome = active_biome.get();
+    moai_texture.head = match &biome.moai_head_texture {
+        Some(path) => asset_server.load(path),
+        None => moai_texture.default_head.clone(),
+    };
+    moai_texture.body = match &biome.moai_body_texture {
+        Some(path) => asset_server.load(path),
+        None => moai_texture.default_body.clone(),
+    };
+}
+
+fn spawn_moai_body_sprites(parent: &mut ChildBuilder, texture: Handle<Image>, tint: Color) {
     for i in 1..=consts::MOAI_BODY_SEGMENTS_COUNT {
         parent.spawn(SpriteBundle {
             texture: texture.clone(),
             sprite: Sprite {
+                color: tint,
                 custom_size: Some(Vec2 {
                     x: consts::MOAI_WIDTH,
                     y: consts::MOAI_HEIGHT,
@@ -84,20 +135,27 @@ fn spawn_moai_body_sprites(parent: &mut ChildBuilder, texture: Handle<Image>) {
             transform: Transform::from_xyz(
                 0.0,
                 (1.0 - consts::MOAI_BODY_SEGMENTS_OVERLAP_RATIO) * consts::MOAI_HEIGHT * -i as f32,
-                -1.0 * i as f32,
+                -(i as f32),
             ),
             ..default()
         });
     }
 }
 
-fn spawn_moai(mut commands: Commands, moai_texture: Res<MoaiTexture>, x: f32) {
-    let mut rng = rand::thread_rng();
+fn spawn_moai(
+    mut commands: Commands,
+    moai_texture: Res<MoaiTexture>,
+    run_info: &mut RunInfo,
+    tint: Color,
+    x: f32,
+) {
+    let height = run_info.rng().gen_range(consts::MOAI_HEIGHT_RANGE.clone());
+    let gap = run_info.difficulty().moai_gap();
 
     commands
         .spawn((
             SpatialBundle::default(),
-            Moai::new(x, rng.gen_range(consts::MOAI_HEIGHT_RANGE.clone())),
+            Moai::new(x, height, gap),
             Name::new("Moai"),
         ))
         .with_children(|parent| {
@@ -106,6 +164,7 @@ fn spawn_moai(mut commands: Commands, moai_texture: Res<MoaiTexture>, x: f32) {
                 .spawn(SpriteBundle {
                     texture: moai_texture.head.clone(),
                     sprite: Sprite {
+                        color: tint,
                         custom_size: Some(Vec2 {
                             x: consts::MOAI_WIDTH,
                             y: consts::MOAI_HEIGHT,
@@ -114,13 +173,16 @@ fn spawn_moai(mut commands: Commands, moai_texture: Res<MoaiTexture>, x: f32) {
                     },
                     ..default()
                 })
-                .with_children(|parent| spawn_moai_body_sprites(parent, moai_texture.body.clone()));
+                .with_children(|parent| {
+                    spawn_moai_body_sprites(parent, moai_texture.body.clone(), tint)
+                });
 
             //up
             parent
                 .spawn(SpriteBundle {
                     texture: moai_texture.head.clone(),
                     sprite: Sprite {
+                        color: tint,
                         custom_size: Some(Vec2 {
                             x: consts::MOAI_WIDTH,
                             y: consts::MOAI_HEIGHT,
@@ -129,17 +191,25 @@ fn spawn_moai(mut commands: Commands, moai_texture: Res<MoaiTexture>, x: f32) {
                     },
                     transform: Transform::from_translation(Vec3 {
                         x: 0.0,
-                        y: consts::MOAI_HEIGHT + consts::MOAI_VERTICAL_DISTANCE,
+                        y: consts::MOAI_HEIGHT + gap,
                         z: 0.0,
                     })
                     .with_rotation(Quat::from_rotation_z(std::f32::consts::PI)),
                     ..default()
                 })
-                .with_children(|parent| spawn_moai_body_sprites(parent, moai_texture.body.clone()));
+                .with_children(|parent| {
+                    spawn_moai_body_sprites(parent, moai_texture.body.clone(), tint)
+                });
         });
 }
 
-fn spawn_init_moai(commands: Commands, game_size: Res<GameSize>, moai_texture: Res<MoaiTexture>) {
+fn spawn_init_moai(
+    commands: Commands,
+    game_size: Res<GameSize>,
+    moai_texture: Res<MoaiTexture>,
+    active_biome: Res<ActiveBiome>,
+    mut run_info: ResMut<RunInfo>,
+) {
     let mut x = if game_size.max_x < game_size.max_y {
         game_size.max_x
     } else {
@@ -148,7 +218,13 @@ fn spawn_init_moai(commands: Commands, game_size: Res<GameSize>, moai_texture: R
     if x < game_size.max_x + consts::MOAI_WIDTH {
         x = game_size.max_x + consts::MOAI_WIDTH;
     }
-    spawn_moai(commands, moai_texture, x)
+    spawn_moai(
+        commands,
+        moai_texture,
+        &mut run_info,
+        active_biome.get().moai_color(),
+        x,
+    )
 }
 
 fn despawn_all_moai(mut commands: Commands, query_all_moai: Query<Entity, With<Moai>>) {
@@ -170,19 +246,13 @@ fn move_moai(
     commands: Commands,
     game_size: Res<GameSize>,
     moai_texture: Res<MoaiTexture>,
-    mut game_score: ResMut<GameScore>,
+    active_biome: Res<ActiveBiome>,
+    mut run_info: ResMut<RunInfo>,
 ) {
+    let biome = active_biome.get();
     let mut max_x = f32::MIN;
     for mut moai in query_all_moai.iter_mut() {
-        let before = moai.x;
-        let after = before - consts::MOAI_MOVE_SPEED * time.delta_seconds();
-
-        if before >= 0.0 && after <= 0.0 && !moai.passed {
-            game_score.inc_score();
-            moai.passed = true;
-        }
-
-        moai.x = after;
+        moai.x -= biome.scroll_speed * time.delta_seconds();
         if moai.x > max_x {
             max_x = moai.x;
         }
@@ -190,7 +260,23 @@ fn move_moai(
 
     if game_size.max_x + consts::MOAI_WIDTH - max_x >= consts::MOAI_HORIZONTAL_DISTANCE {
         let x = game_size.max_x + consts::MOAI_WIDTH;
-        spawn_moai(commands, moai_texture, x);
+        spawn_moai(commands, moai_texture, &mut run_info, biome.moai_color(), x);
+    }
+}
+
+pub fn pass_moai(
+    query_pebble: Query<&Pebble>,
+    mut query_all_moai: Query<&mut Moai>,
+    mut passed_events: EventWriter<MoaiPassed>,
+) {
+    let Ok(pebble) = query_pebble.get_single() else {
+        return;
+    };
+    for mut moai in query_all_moai.iter_mut() {
+        if !moai.passed && moai.x <= pebble.x() {
+            moai.passed = true;
+            passed_events.send(MoaiPassed);
+        }
     }
 }
 
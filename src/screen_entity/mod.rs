@@ -1,6 +1,10 @@
+mod background;
 mod moai;
 mod pebble;
 
+pub use moai::{pass_moai, Moai, MoaiPassed};
+pub use pebble::{check_collisions, DeathCause, MoaiCleared, Pebble, PebbleDied, PebbleFlapped};
+
 pub struct GameEntityPlugin;
 
 impl bevy::app::PluginGroup for GameEntityPlugin {
@@ -8,5 +12,6 @@ impl bevy::app::PluginGroup for GameEntityPlugin {
         bevy::app::PluginGroupBuilder::start::<Self>()
             .add(pebble::PebblePlugin)
             .add(moai::MoaiPlugin)
+            .add(background::BackgroundPlugin)
     }
 }
@@ -1,12 +1,14 @@
+use crate::biome::ActiveBiome;
 use crate::consts;
-use bevy::input::common_conditions::input_just_pressed;
 use bevy::prelude::*;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::game_size::GameSize;
-use crate::gamepad_util::gamepad_just_pressed;
+use crate::input::{action_just_pressed, Action, ActionInput, InputSource};
 use crate::state::gamestate::GameState;
-use crate::touch_util::touch_just_pressed;
+use crate::state::play_phase::{GameplaySet, PlayPhase};
+use crate::state::run_info::{setup_run, RunInfo};
 
 use super::moai::Moai;
 
@@ -16,46 +18,95 @@ pub struct PebblePlugin;
 impl Plugin for PebblePlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Pebble>()
+            .add_event::<PebbleFlapped>()
+            .add_event::<PebbleDied>()
+            .add_event::<MoaiCleared>()
             .add_systems(
                 OnTransition {
                     from: GameState::MainMenu,
                     to: GameState::Playing,
                 },
-                (despawn_pebble, spawn_pebble).chain(),
+                (despawn_pebble, spawn_pebble).chain().after(setup_run),
             )
             .add_systems(
                 OnTransition {
                     from: GameState::GameOver,
                     to: GameState::Playing,
                 },
-                (despawn_pebble, spawn_pebble).chain(),
+                (despawn_pebble, spawn_pebble).chain().after(setup_run),
             )
             .add_systems(OnEnter(GameState::MainMenu), despawn_pebble)
             .add_systems(
                 FixedUpdate,
-                (pebble_move, check_death_down, check_collisions)
+                (pebble_move, check_death_down, check_collisions).in_set(GameplaySet),
+            )
+            .add_systems(
+                Update,
+                (
+                    bob_pebble.run_if(resource_equals(PlayPhase::Ready)),
+                    render_pebble,
+                )
+                    .chain()
                     .run_if(in_state(GameState::Playing)),
             )
-            .add_systems(Update, render_pebble.run_if(in_state(GameState::Playing)))
             .add_systems(
                 Update,
-                reset_pebble_velocity.run_if(
-                    in_state(GameState::Playing).and_then(
-                        input_just_pressed(MouseButton::Left)
-                            .or_else(input_just_pressed(KeyCode::Space))
-                            .or_else(gamepad_just_pressed(GamepadButtonType::South))
-                            .or_else(touch_just_pressed()),
-                    ),
-                ),
+                reset_pebble_velocity
+                    .in_set(GameplaySet)
+                    .run_if(action_just_pressed(Action::Flap)),
             );
     }
 }
 
+#[derive(Debug, Event)]
+pub struct PebbleFlapped {
+    //the device the flap was pressed on
+    pub source: InputSource,
+}
+
+//ends the run, see `state::leaderboard::finish_run`
+#[derive(Debug, Event)]
+pub struct PebbleDied {
+    pub cause: DeathCause,
+}
+
+//sent once the pebble is fully past a moai
+#[derive(Debug, Event)]
+pub struct MoaiCleared {
+    //smallest vertical space left between the pebble and the moai
+    pub clearance: f32,
+    //the clearance when passing right through the middle of the gap
+    pub max_clearance: f32,
+    pub gap_centre: Vec2,
+}
+
+impl MoaiCleared {
+    pub fn is_near_miss(&self) -> bool {
+        self.clearance < consts::NEAR_MISS_DISTANCE
+    }
+
+    //1 in the middle of the gap, 0 when touching the moai
+    pub fn accuracy(&self) -> f32 {
+        if self.max_clearance <= 0.0 {
+            return 0.0;
+        }
+        (self.clearance / self.max_clearance).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DeathCause {
+    Floor,
+    MoaiTop,
+    MoaiBottom,
+}
+
 #[derive(Debug, Component, Reflect)]
 pub struct Pebble {
     velocity: f32,
     x: f32,
     y: f32,
+    start_y: f32,
 }
 
 impl Pebble {
@@ -64,12 +115,24 @@ impl Pebble {
             velocity,
             x: 0.0,
             y,
+            start_y: y,
         }
     }
+
+    pub fn x(&self) -> f32 {
+        self.x
+    }
+    pub fn y(&self) -> f32 {
+        self.y
+    }
 }
 
-fn spawn_pebble(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let mut rng = rand::thread_rng();
+fn spawn_pebble(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut run_info: ResMut<RunInfo>,
+) {
+    let rng = run_info.rng();
 
     commands.spawn((
         SpriteBundle {
@@ -92,11 +155,24 @@ fn spawn_pebble(mut commands: Commands, asset_server: Res<AssetServer>) {
     ));
 }
 
-fn pebble_move(time: Res<Time<Fixed>>, mut pebble: Query<&mut Pebble>) {
+fn pebble_move(
+    time: Res<Time<Fixed>>,
+    active_biome: Res<ActiveBiome>,
+    mut pebble: Query<&mut Pebble>,
+) {
     let mut pebble = pebble.get_single_mut().expect("to get a pebble");
+    let biome = active_biome.get();
+    let acceleration = biome.acceleration();
     pebble.y += pebble.velocity * time.delta_seconds()
-        + consts::G_FORCE_ACCELERATION * time.delta_seconds() * time.delta_seconds() / 2.0;
-    pebble.velocity += consts::G_FORCE_ACCELERATION * time.delta_seconds();
+        + acceleration * time.delta_seconds() * time.delta_seconds() / 2.0;
+    pebble.velocity += acceleration * time.delta_seconds();
+    pebble.velocity *= f32::max(1.0 - biome.drag * time.delta_seconds(), 0.0);
+}
+
+fn bob_pebble(time: Res<Time>, mut pebble: Query<&mut Pebble>) {
+    let mut pebble = pebble.get_single_mut().expect("to get a pebble");
+    pebble.y = pebble.start_y
+        + (time.elapsed_seconds() * consts::PEBBLE_BOB_SPEED).sin() * consts::PEBBLE_BOB_HEIGHT;
 }
 
 fn render_pebble(
@@ -111,44 +187,79 @@ fn render_pebble(
     transform.translation.y = pebble.y;
 }
 
-fn reset_pebble_velocity(mut pebble: Query<&mut Pebble>) {
+fn reset_pebble_velocity(
+    active_biome: Res<ActiveBiome>,
+    action_input: ActionInput,
+    mut pebble: Query<&mut Pebble>,
+    mut flap_events: EventWriter<PebbleFlapped>,
+) {
+    let Some(flap) = action_input
+        .read()
+        .find(|event| event.action == Action::Flap)
+    else {
+        return;
+    };
     let mut pebble = pebble.get_single_mut().expect("to get a pebble");
-    pebble.velocity = consts::PEBBLE_DEFAULT_VELOCITY;
+    pebble.velocity = active_biome.get().flap_velocity;
+    flap_events.send(PebbleFlapped {
+        source: flap.source,
+    });
 }
 
 fn check_death_down(
     query_pebble: Query<&Pebble>,
     game_size: Res<GameSize>,
-    mut game_state: ResMut<NextState<GameState>>,
+    mut death_events: EventWriter<PebbleDied>,
 ) {
     let pebble = query_pebble.get_single().expect("to get a pebble");
     if pebble.y < game_size.min_y {
-        game_state.set(GameState::GameOver)
+        death_events.send(PebbleDied {
+            cause: DeathCause::Floor,
+        });
     }
 }
 
 //to run this check in FixedUpdate, collisions are going to be checked manually
-fn check_collisions(
+pub fn check_collisions(
     query_pebble: Query<&Pebble>,
-    query_moai: Query<&Moai>,
-    mut game_state: ResMut<NextState<GameState>>,
+    mut query_moai: Query<&mut Moai>,
+    mut death_events: EventWriter<PebbleDied>,
+    mut cleared_events: EventWriter<MoaiCleared>,
 ) {
     let pebble = query_pebble.get_single().expect("to get a pebble");
-    for moai in query_moai.iter() {
+    for mut moai in query_moai.iter_mut() {
         let already_passed =
             moai.x + consts::MOAI_WIDTH / 2.0 < pebble.x - consts::PEBBLE_WIDTH / 2.0;
         let not_reached_yet =
             moai.x - consts::MOAI_WIDTH / 2.0 > pebble.x + consts::PEBBLE_WIDTH / 2.0;
+        if already_passed && !moai.cleared {
+            moai.cleared = true;
+            cleared_events.send(MoaiCleared {
+                clearance: moai.clearance,
+                max_clearance: (moai.gap - consts::PEBBLE_HEIGHT) / 2.0,
+                gap_centre: Vec2::new(moai.x, moai.height + moai.gap / 2.0),
+            });
+        }
         if not_reached_yet || already_passed {
             continue;
         }
 
-        let collided_down = moai.height > pebble.y - consts::PEBBLE_HEIGHT / 2.0;
-        let up_moai_start_y = moai.height + consts::MOAI_VERTICAL_DISTANCE;
-        let collided_up = up_moai_start_y < pebble.y + consts::PEBBLE_HEIGHT / 2.0;
+        let space_down = pebble.y - consts::PEBBLE_HEIGHT / 2.0 - moai.height;
+        let up_moai_start_y = moai.height + moai.gap;
+        let space_up = up_moai_start_y - (pebble.y + consts::PEBBLE_HEIGHT / 2.0);
+        moai.clearance = moai.clearance.min(space_down).min(space_up);
+
+        let collided_down = space_down < 0.0;
+        let collided_up = space_up < 0.0;
 
         if collided_down || collided_up {
-            game_state.set(GameState::GameOver);
+            let cause = if collided_down {
+                DeathCause::MoaiBottom
+            } else {
+                DeathCause::MoaiTop
+            };
+            death_events.send(PebbleDied { cause });
+            return;
         }
     }
 }
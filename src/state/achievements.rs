@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::gamescore::{GameScore, Score};
+use super::gamestate::GameState;
+use super::run_info::{setup_run, RunInfo};
+use super::save_file::SaveData;
+use super::settings::GameMode;
+use super::statistics::{record_statistics, RunStatistics};
+use super::timestamp::{now, Timestamp};
+use crate::consts;
+use crate::ron_asset::RonAssetLoader;
+use crate::screen_entity::{DeathCause, Moai, MoaiPassed, Pebble, PebbleDied};
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Achievements>()
+            .register_asset_loader(RonAssetLoader::<Achievements>::new(&["achievements.ron"]))
+            .init_resource::<RunAchievementProgress>()
+            .add_event::<AchievementUnlocked>()
+            .add_systems(Startup, load_achievements)
+            .add_systems(
+                OnTransition {
+                    from: GameState::MainMenu,
+                    to: GameState::Playing,
+                },
+                reset_run_progress.after(setup_run),
+            )
+            .add_systems(
+                OnTransition {
+                    from: GameState::GameOver,
+                    to: GameState::Playing,
+                },
+                reset_run_progress.after(setup_run),
+            )
+            .add_systems(
+                Update,
+                (
+                    track_low_passes,
+                    count_first_moai_deaths,
+                    unlock_achievements,
+                )
+                    .chain()
+                    .after(record_statistics)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+//Achievements are described in a data file (see `consts::ACHIEVEMENTS_PATH`).
+//The id is what the save file remembers, so it must not change once released.
+#[derive(Debug, Asset, TypePath, Deserialize)]
+pub struct Achievements {
+    pub achievements: Vec<Achievement>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Achievement {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub condition: AchievementCondition,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum AchievementCondition {
+    //in a single ranked classic run, other modes don't score points
+    Score(Score),
+    //moai passed in a row without the pebble going above the middle of the gap
+    LowPassStreak(u32),
+    GamesPlayed(u32),
+    //runs that ended on a moai before any point was scored
+    FirstMoaiDeaths(u32),
+}
+
+//Stored in the save file. Also keeps the counters that no other statistic
+//covers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AchievementProgress {
+    unlocked: BTreeMap<String, Timestamp>,
+    first_moai_deaths: u32,
+}
+
+impl AchievementProgress {
+    pub fn unlocked_at(&self, id: &str) -> Option<Timestamp> {
+        self.unlocked.get(id).copied()
+    }
+}
+
+#[derive(Debug, Event)]
+pub struct AchievementUnlocked {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Resource)]
+pub struct AchievementsHandle(pub Handle<Achievements>);
+
+#[derive(Debug, Resource, Default)]
+struct RunAchievementProgress {
+    low_pass_streak: u32,
+    //since the last moai was passed
+    above_midpoint: bool,
+}
+
+fn load_achievements(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(AchievementsHandle(
+        asset_server.load(consts::ACHIEVEMENTS_PATH),
+    ));
+}
+
+fn reset_run_progress(mut run_progress: ResMut<RunAchievementProgress>) {
+    *run_progress = RunAchievementProgress::default();
+}
+
+fn track_low_passes(
+    mut passed_events: EventReader<MoaiPassed>,
+    query_pebble: Query<&Pebble>,
+    query_moai: Query<&Moai>,
+    mut run_progress: ResMut<RunAchievementProgress>,
+) {
+    for _ in passed_events.read() {
+        if run_progress.above_midpoint {
+            run_progress.low_pass_streak = 0;
+        } else {
+            run_progress.low_pass_streak += 1;
+        }
+        run_progress.above_midpoint = false;
+    }
+
+    let Ok(pebble) = query_pebble.get_single() else {
+        return;
+    };
+    let next_moai = query_moai
+        .iter()
+        .filter(|moai| !moai.passed)
+        .min_by(|moai, other| moai.x.total_cmp(&other.x));
+    if let Some(moai) = next_moai {
+        if pebble.y() > moai.height + moai.gap / 2.0 {
+            run_progress.above_midpoint = true;
+        }
+    }
+}
+
+//only the first death of the run counts, like in the statistics
+fn count_first_moai_deaths(
+    mut death_events: EventReader<PebbleDied>,
+    run_statistics: Res<RunStatistics>,
+    mut save_data: ResMut<SaveData>,
+) {
+    let Some(cause) = death_events.read().next().map(|death| death.cause) else {
+        return;
+    };
+    death_events.clear();
+
+    if cause != DeathCause::Floor && run_statistics.moai_passed() == 0 {
+        save_data.achievements_mut().first_moai_deaths += 1;
+    }
+}
+
+fn unlock_achievements(
+    achievements_handle: Res<AchievementsHandle>,
+    achievements: Res<Assets<Achievements>>,
+    game_score: Res<GameScore>,
+    run_info: Res<RunInfo>,
+    run_progress: Res<RunAchievementProgress>,
+    mut save_data: ResMut<SaveData>,
+    mut unlocked_events: EventWriter<AchievementUnlocked>,
+) {
+    let Some(achievements) = achievements.get(&achievements_handle.0) else {
+        return;
+    };
+    for achievement in achievements.achievements.iter() {
+        if save_data
+            .achievements()
+            .unlocked_at(&achievement.id)
+            .is_some()
+        {
+            continue;
+        }
+        let reached = match achievement.condition {
+            AchievementCondition::Score(score) => {
+                run_info.game_mode() == GameMode::Classic
+                    && run_info.is_ranked()
+                    && game_score.get_current_score() >= score
+            }
+            AchievementCondition::LowPassStreak(streak) => run_progress.low_pass_streak >= streak,
+            AchievementCondition::GamesPlayed(games) => save_data.statistics().games() >= games,
+            AchievementCondition::FirstMoaiDeaths(deaths) => {
+                save_data.achievements().first_moai_deaths >= deaths
+            }
+        };
+        if !reached {
+            continue;
+        }
+
+        info!("Achievement unlocked: {}", achievement.name);
+        save_data
+            .achievements_mut()
+            .unlocked
+            .insert(achievement.id.clone(), now());
+        unlocked_events.send(AchievementUnlocked {
+            name: achievement.name.clone(),
+            description: achievement.description.clone(),
+        });
+    }
+}
@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+
+use super::gamescore::{GameScore, Score, ScoreEvent};
+use super::gamestate::GameState;
+use super::play_phase::GameplaySet;
+use super::run_info::{setup_run, RunInfo};
+use crate::consts;
+use crate::screen_entity::{check_collisions, MoaiCleared};
+
+pub struct BonusScoringPlugin;
+
+impl Plugin for BonusScoringPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Combo>()
+            .add_event::<BonusScored>()
+            .add_systems(
+                OnTransition {
+                    from: GameState::MainMenu,
+                    to: GameState::Playing,
+                },
+                reset_combo.after(setup_run),
+            )
+            .add_systems(
+                OnTransition {
+                    from: GameState::GameOver,
+                    to: GameState::Playing,
+                },
+                reset_combo.after(setup_run),
+            )
+            //in the same fixed steps as the points of the scoring rule
+            .add_systems(
+                FixedUpdate,
+                award_bonus_points
+                    .after(check_collisions)
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+//stylish ways to pass a moai, each worth extra points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BonusKind {
+    NearMiss,
+    Centre,
+}
+
+impl BonusKind {
+    //None for a plain pass
+    pub fn of(cleared: &MoaiCleared) -> Option<BonusKind> {
+        if cleared.is_near_miss() {
+            Some(BonusKind::NearMiss)
+        } else if cleared.accuracy() >= consts::CENTRE_BAND_ACCURACY {
+            Some(BonusKind::Centre)
+        } else {
+            None
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BonusKind::NearMiss => "NEAR MISS",
+            BonusKind::Centre => "CENTRE",
+        }
+    }
+
+    fn points(self) -> Score {
+        match self {
+            BonusKind::NearMiss => consts::NEAR_MISS_BONUS,
+            BonusKind::Centre => consts::CENTRE_BONUS,
+        }
+    }
+}
+
+#[derive(Debug, Event)]
+pub struct BonusScored {
+    pub kind: BonusKind,
+    //already multiplied
+    pub points: Score,
+    pub multiplier: u32,
+    pub position: Vec2,
+}
+
+//stylish passes in a row, a plain one breaks the combo
+#[derive(Debug, Resource, Default)]
+struct Combo(u32);
+
+fn reset_combo(mut combo: ResMut<Combo>) {
+    combo.0 = 0;
+}
+
+//only in runs started with bonus scoring on
+fn award_bonus_points(
+    mut cleared_events: EventReader<MoaiCleared>,
+    run_info: Res<RunInfo>,
+    mut combo: ResMut<Combo>,
+    mut game_score: ResMut<GameScore>,
+    mut score_events: EventWriter<ScoreEvent>,
+    mut bonus_events: EventWriter<BonusScored>,
+) {
+    if !run_info.bonus_scoring() {
+        cleared_events.clear();
+        return;
+    }
+
+    for cleared in cleared_events.read() {
+        let Some(kind) = BonusKind::of(cleared) else {
+            combo.0 = 0;
+            continue;
+        };
+
+        combo.0 += 1;
+        let multiplier = combo.0.min(consts::MAX_COMBO_MULTIPLIER);
+        let points = kind.points() * multiplier;
+        let was_high_score = game_score.is_new_high_score();
+        game_score.add_points(points);
+        score_events.send(ScoreEvent::Increased);
+        if !was_high_score && game_score.is_new_high_score() {
+            score_events.send(ScoreEvent::NewHighScore);
+        }
+        bonus_events.send(BonusScored {
+            kind,
+            points,
+            multiplier,
+            position: cleared.gap_centre,
+        });
+    }
+}
@@ -1,21 +1,49 @@
 use bevy::prelude::*;
 
 use super::gamestate::GameState;
-use crate::consts::HIGHSCORE_PATH;
-
-type Score = u32;
+use super::play_phase::GameplaySet;
+use super::run_info::{setup_run, RunInfo};
+use super::save_file::SaveData;
+use super::scoring_rule::{PerMoai, ScoringContext, ScoringRule};
+use crate::biome::ActiveBiome;
+use crate::screen_entity::{pass_moai, MoaiPassed};
+
+pub type Score = u32;
+
+#[derive(Debug, Event)]
+pub enum ScoreEvent {
+    Increased,
+    NewHighScore,
+}
 
-#[derive(Debug, Resource, Default)]
+//The score of the current run, counted by the rule of the run's game mode.
+//The high score is the one of that game mode.
+#[derive(Debug, Resource)]
 pub struct GameScore {
     current_score: Score,
     high_score: Score,
     is_high_score: bool,
+    //unranked runs can't beat the high score, see `RunInfo::is_ranked`
+    ranked: bool,
+    rule: Box<dyn ScoringRule>,
+}
+
+impl Default for GameScore {
+    fn default() -> Self {
+        GameScore {
+            current_score: 0,
+            high_score: 0,
+            is_high_score: false,
+            ranked: true,
+            rule: Box::new(PerMoai),
+        }
+    }
 }
 
 impl GameScore {
-    pub fn inc_score(&mut self) {
-        self.current_score += 1;
-        if self.current_score > self.high_score {
+    pub fn add_points(&mut self, points: Score) {
+        self.current_score += points;
+        if self.ranked && self.current_score > self.high_score {
             self.is_high_score = true;
         }
     }
@@ -28,6 +56,20 @@ impl GameScore {
     pub fn is_new_high_score(&self) -> bool {
         self.is_high_score
     }
+    //what the score measures, e.g. "Distance"
+    pub fn label(&self) -> &'static str {
+        self.rule.label()
+    }
+    pub fn units(&self) -> &'static str {
+        self.rule.units()
+    }
+    pub fn rule(&self) -> &dyn ScoringRule {
+        self.rule.as_ref()
+    }
+    //with the units, e.g. "12 m"
+    pub fn format_score(&self, score: Score) -> String {
+        self.rule.format_score(score)
+    }
 }
 
 pub struct GameScorePlugin;
@@ -35,7 +77,25 @@ pub struct GameScorePlugin;
 impl Plugin for GameScorePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<GameScore>()
-            .add_systems(Startup, load_highscore)
+            .add_event::<ScoreEvent>()
+            .add_systems(
+                OnTransition {
+                    from: GameState::MainMenu,
+                    to: GameState::Playing,
+                },
+                start_scoring.after(setup_run),
+            )
+            .add_systems(
+                OnTransition {
+                    from: GameState::GameOver,
+                    to: GameState::Playing,
+                },
+                start_scoring.after(setup_run),
+            )
+            .add_systems(
+                FixedUpdate,
+                apply_scoring_rule.after(pass_moai).in_set(GameplaySet),
+            )
             .add_systems(OnEnter(GameState::GameOver), handle_highscore)
             .add_systems(OnExit(GameState::GameOver), reset_score);
     }
@@ -46,61 +106,49 @@ fn reset_score(mut game_score: ResMut<GameScore>) {
     game_score.is_high_score = false;
 }
 
-#[cfg(not(target_family = "wasm"))]
-fn save_highscore(highscore: Score) {
-    use std::{fs::File, io::Write};
-
-    use bevy::tasks::IoTaskPool;
-
-    IoTaskPool::get()
-        .spawn(async move {
-            File::create(format!("assets/{HIGHSCORE_PATH}"))
-                .and_then(|mut file| file.write(&highscore.to_be_bytes()))
-                .unwrap_or_else(|_| {
-                    warn!("Could not write highscore");
-                    0
-                });
-        })
-        .detach();
+pub fn start_scoring(
+    run_info: Res<RunInfo>,
+    save_data: Res<SaveData>,
+    mut game_score: ResMut<GameScore>,
+) {
+    let game_mode = run_info.game_mode();
+    game_score.rule = game_mode.scoring_rule();
+    game_score.ranked = run_info.is_ranked();
+    game_score.high_score = save_data.high_score(game_mode);
 }
 
-fn handle_highscore(mut game_score: ResMut<GameScore>) {
-    if game_score.is_high_score {
-        game_score.high_score = game_score.current_score;
-        save_highscore(game_score.high_score);
+//in fixed steps like the world itself, so scores don't depend on frame rate
+fn apply_scoring_rule(
+    time: Res<Time<Fixed>>,
+    active_biome: Res<ActiveBiome>,
+    mut passed_events: EventReader<MoaiPassed>,
+    mut game_score: ResMut<GameScore>,
+    mut score_events: EventWriter<ScoreEvent>,
+) {
+    let context = ScoringContext {
+        delta_seconds: time.delta_seconds(),
+        distance: active_biome.get().scroll_speed * time.delta_seconds(),
+        moai_passed: passed_events.read().count() as u32,
+    };
+    let points = game_score.rule.update(&context);
+    if points == 0 {
+        return;
     }
-}
-
-#[cfg(target_family = "wasm")]
-fn save_highscore(highscore: Score) {
-    use gloo_storage::{LocalStorage, Storage};
-
-    LocalStorage::set(HIGHSCORE_PATH, highscore).unwrap_or_else(|_| {
-        warn!("Could not save highscore");
-    });
-}
 
-#[cfg(not(target_family = "wasm"))]
-fn load_highscore(mut score: ResMut<GameScore>) {
-    use std::{fs::File, io::Read};
-    let mut read_highscore: Score = 0;
-    let mut buffer = read_highscore.to_be_bytes();
-    File::open(format!("assets/{HIGHSCORE_PATH}"))
-        .and_then(|mut file| file.read(&mut buffer))
-        .unwrap_or_else(|_| {
-            warn!("Could not read highscore");
-            0
-        });
-    read_highscore = Score::from_be_bytes(buffer);
-    score.high_score = read_highscore;
+    let was_high_score = game_score.is_new_high_score();
+    game_score.add_points(points);
+    score_events.send(ScoreEvent::Increased);
+    if !was_high_score && game_score.is_new_high_score() {
+        score_events.send(ScoreEvent::NewHighScore);
+    }
 }
 
-#[cfg(target_family = "wasm")]
-fn load_highscore(mut score: ResMut<GameScore>) {
-    use gloo_storage::{LocalStorage, Storage};
-
-    score.high_score = LocalStorage::get(HIGHSCORE_PATH).unwrap_or_else(|_| {
-        warn!("Could not read highscore");
-        Score::default()
-    });
+fn handle_highscore(
+    game_score: Res<GameScore>,
+    run_info: Res<RunInfo>,
+    mut save_data: ResMut<SaveData>,
+) {
+    if game_score.is_high_score {
+        save_data.set_high_score(run_info.game_mode(), game_score.current_score);
+    }
 }
@@ -1,12 +1,22 @@
 use bevy::prelude::*;
+use serde::Deserialize;
 
-#[derive(Debug, Default, Clone, Copy, States, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(
+    Debug, Default, Clone, Copy, States, Hash, PartialEq, Eq, PartialOrd, Ord, Deserialize,
+)]
 pub enum GameState {
     #[default]
     MainMenu,
     Playing,
+    //a run that made the leaderboard is named before the results
+    NameEntry,
     GameOver,
     Pause,
+    Settings,
+    Controls,
+    Leaderboard,
+    Statistics,
+    Achievements,
     Exit,
 }
 
@@ -0,0 +1,203 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::gamescore::{GameScore, Score};
+use super::gamestate::GameState;
+use super::run_info::{RunInfo, Seed};
+use super::save_file::SaveData;
+use super::settings::Difficulty;
+use super::timestamp::{format_date, now, Timestamp};
+use crate::consts::LEADERBOARD_SIZE;
+use crate::screen_entity::PebbleDied;
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LastRunRank>()
+            .add_systems(
+                Update,
+                finish_run.run_if(in_state(GameState::Playing).and_then(on_event::<PebbleDied>())),
+            )
+            .add_systems(OnEnter(GameState::GameOver), record_run);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    name: String,
+    score: Score,
+    timestamp: Timestamp,
+    difficulty: Difficulty,
+    seed: Seed,
+}
+
+impl LeaderboardEntry {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn score(&self) -> Score {
+        self.score
+    }
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+    pub fn seed(&self) -> Seed {
+        self.seed
+    }
+
+    pub fn date(&self) -> String {
+        format_date(self.timestamp)
+    }
+}
+
+//The best runs, best first. An older run keeps its place on equal scores.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Leaderboard(Vec<LeaderboardEntry>);
+
+impl Leaderboard {
+    pub const fn new() -> Self {
+        Leaderboard(Vec::new())
+    }
+
+    pub fn entries(&self) -> &[LeaderboardEntry] {
+        &self.0
+    }
+
+    pub fn qualifies(&self, score: Score) -> bool {
+        score > 0
+            && (self.0.len() < LEADERBOARD_SIZE
+                || self.0.last().is_some_and(|last| last.score < score))
+    }
+
+    //returns the rank of the entry if it made it into the table
+    fn insert(&mut self, entry: LeaderboardEntry) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+        let rank = self
+            .0
+            .iter()
+            .position(|other| other.score < entry.score)
+            .unwrap_or(self.0.len());
+        self.0.insert(rank, entry);
+        self.0.truncate(LEADERBOARD_SIZE);
+        Some(rank)
+    }
+}
+
+//Rank of the last run on the leaderboard, 0 is the top.
+#[derive(Debug, Resource, Default)]
+pub struct LastRunRank(pub Option<usize>);
+
+//runs that make it onto the leaderboard are named before the results
+fn finish_run(
+    game_score: Res<GameScore>,
+    run_info: Res<RunInfo>,
+    save_data: Res<SaveData>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let qualifies = run_info.is_ranked()
+        && save_data
+            .leaderboard(run_info.game_mode())
+            .qualifies(game_score.get_current_score());
+    next_state.set(if qualifies {
+        GameState::NameEntry
+    } else {
+        GameState::GameOver
+    });
+}
+
+pub fn record_run(
+    game_score: Res<GameScore>,
+    run_info: Res<RunInfo>,
+    mut save_data: ResMut<SaveData>,
+    mut last_run_rank: ResMut<LastRunRank>,
+) {
+    if !run_info.is_ranked() {
+        last_run_rank.0 = None;
+        return;
+    }
+    let entry = LeaderboardEntry {
+        name: save_data.player_name().to_string(),
+        score: game_score.get_current_score(),
+        timestamp: now(),
+        difficulty: run_info.difficulty(),
+        seed: run_info.seed(),
+    };
+    last_run_rank.0 = save_data
+        .leaderboard_mut(run_info.game_mode())
+        .insert(entry);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, score: Score) -> LeaderboardEntry {
+        LeaderboardEntry {
+            name: name.to_string(),
+            score,
+            timestamp: 0,
+            difficulty: Difficulty::Normal,
+            seed: 0,
+        }
+    }
+
+    fn full_leaderboard() -> Leaderboard {
+        let mut leaderboard = Leaderboard::new();
+        for score in 1..=LEADERBOARD_SIZE as Score {
+            leaderboard.insert(entry("FULL", score * 10));
+        }
+        leaderboard
+    }
+
+    fn scores(leaderboard: &Leaderboard) -> Vec<Score> {
+        leaderboard
+            .entries()
+            .iter()
+            .map(|entry| entry.score())
+            .collect()
+    }
+
+    #[test]
+    fn zero_never_qualifies() {
+        let mut leaderboard = Leaderboard::new();
+        assert!(!leaderboard.qualifies(0));
+        assert_eq!(leaderboard.insert(entry("ZERO", 0)), None);
+        assert!(leaderboard.entries().is_empty());
+    }
+
+    #[test]
+    fn tie_ranks_below_the_older_entry() {
+        let mut leaderboard = Leaderboard::new();
+        leaderboard.insert(entry("FIRST", 5));
+        assert_eq!(leaderboard.insert(entry("SECOND", 5)), Some(1));
+        assert_eq!(leaderboard.insert(entry("BEST", 6)), Some(0));
+
+        let names: Vec<&str> = leaderboard.entries().iter().map(|e| e.name()).collect();
+        assert_eq!(names, ["BEST", "FIRST", "SECOND"]);
+    }
+
+    #[test]
+    fn full_table_needs_more_than_the_last_score() {
+        let mut leaderboard = full_leaderboard();
+        assert!(!leaderboard.qualifies(9));
+        assert!(!leaderboard.qualifies(10));
+        assert!(leaderboard.qualifies(11));
+        assert_eq!(leaderboard.insert(entry("TIE", 10)), None);
+        assert_eq!(leaderboard.entries().len(), LEADERBOARD_SIZE);
+    }
+
+    #[test]
+    fn insert_into_full_table_drops_the_last_entry() {
+        let mut leaderboard = full_leaderboard();
+        assert_eq!(leaderboard.insert(entry("NEW", 55)), Some(5));
+
+        let scores = scores(&leaderboard);
+        assert_eq!(scores.len(), LEADERBOARD_SIZE);
+        assert_eq!(scores.first(), Some(&100));
+        assert_eq!(scores[5], 55);
+        assert_eq!(scores.last(), Some(&20));
+    }
+}
@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::gamescore::Score;
+use crate::consts;
+use crate::ron_asset::RonAssetLoader;
+
+pub struct MedalsPlugin;
+
+impl Plugin for MedalsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<MedalThresholds>()
+            .register_asset_loader(RonAssetLoader::<MedalThresholds>::new(&["medals.ron"]))
+            .add_systems(Startup, load_medal_thresholds);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Medal {
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+}
+
+impl Medal {
+    pub fn label(self) -> &'static str {
+        match self {
+            Medal::Bronze => "BRONZE",
+            Medal::Silver => "SILVER",
+            Medal::Gold => "GOLD",
+            Medal::Platinum => "PLATINUM",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Medal::Bronze => Color::rgb(0.8, 0.5, 0.2),
+            Medal::Silver => Color::SILVER,
+            Medal::Gold => Color::GOLD,
+            Medal::Platinum => Color::rgb(0.9, 0.95, 1.0),
+        }
+    }
+}
+
+//Lowest score for each medal, see `consts::MEDALS_PATH`.
+#[derive(Debug, Asset, TypePath, Deserialize)]
+pub struct MedalThresholds {
+    pub bronze: Score,
+    pub silver: Score,
+    pub gold: Score,
+    pub platinum: Score,
+}
+
+impl MedalThresholds {
+    //the best medal the score is enough for
+    pub fn medal(&self, score: Score) -> Option<Medal> {
+        [
+            (Medal::Platinum, self.platinum),
+            (Medal::Gold, self.gold),
+            (Medal::Silver, self.silver),
+            (Medal::Bronze, self.bronze),
+        ]
+        .into_iter()
+        .find(|(_, threshold)| score >= *threshold)
+        .map(|(medal, _)| medal)
+    }
+}
+
+#[derive(Resource)]
+pub struct MedalThresholdsHandle(pub Handle<MedalThresholds>);
+
+fn load_medal_thresholds(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(MedalThresholdsHandle(
+        asset_server.load(consts::MEDALS_PATH),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLDS: MedalThresholds = MedalThresholds {
+        bronze: 10,
+        silver: 25,
+        gold: 50,
+        platinum: 100,
+    };
+
+    #[test]
+    fn below_bronze_earns_nothing() {
+        assert_eq!(THRESHOLDS.medal(0), None);
+        assert_eq!(THRESHOLDS.medal(9), None);
+    }
+
+    #[test]
+    fn threshold_itself_earns_the_medal() {
+        assert_eq!(THRESHOLDS.medal(10), Some(Medal::Bronze));
+        assert_eq!(THRESHOLDS.medal(25), Some(Medal::Silver));
+        assert_eq!(THRESHOLDS.medal(50), Some(Medal::Gold));
+        assert_eq!(THRESHOLDS.medal(100), Some(Medal::Platinum));
+    }
+
+    #[test]
+    fn just_below_a_threshold_earns_the_medal_before() {
+        assert_eq!(THRESHOLDS.medal(24), Some(Medal::Bronze));
+        assert_eq!(THRESHOLDS.medal(49), Some(Medal::Silver));
+        assert_eq!(THRESHOLDS.medal(99), Some(Medal::Gold));
+        assert_eq!(THRESHOLDS.medal(Score::MAX), Some(Medal::Platinum));
+    }
+}
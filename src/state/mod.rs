@@ -1,5 +1,17 @@
+pub mod achievements;
+pub mod bonus_scoring;
 pub mod gamescore;
 pub mod gamestate;
+pub mod leaderboard;
+pub mod medals;
+pub mod pause_reason;
+pub mod play_phase;
+pub mod run_info;
+pub mod save_file;
+pub mod scoring_rule;
+pub mod settings;
+pub mod statistics;
+pub mod timestamp;
 
 pub struct StatePlugin;
 
@@ -7,6 +19,16 @@ impl bevy::app::PluginGroup for StatePlugin {
     fn build(self) -> bevy::app::PluginGroupBuilder {
         bevy::app::PluginGroupBuilder::start::<Self>()
             .add(gamestate::GameStatePlugin)
+            .add(save_file::SaveFilePlugin)
             .add(gamescore::GameScorePlugin)
+            .add(leaderboard::LeaderboardPlugin)
+            .add(settings::SettingsPlugin)
+            .add(pause_reason::AutoPausePlugin)
+            .add(play_phase::PlayPhasePlugin)
+            .add(run_info::RunInfoPlugin)
+            .add(statistics::StatisticsPlugin)
+            .add(achievements::AchievementsPlugin)
+            .add(medals::MedalsPlugin)
+            .add(bonus_scoring::BonusScoringPlugin)
     }
 }
@@ -0,0 +1,124 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use bevy::input::gamepad::GamepadConnectionEvent;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::window::WindowFocused;
+
+use super::gamestate::GameState;
+use crate::input::ActiveGamepadDisconnected;
+
+//Why the game is paused, shown in the pause menu.
+#[derive(Debug, Resource, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PauseReason {
+    #[default]
+    Manual,
+    FocusLost,
+    PageHidden,
+    ControllerDisconnected(Gamepad),
+}
+
+impl PauseReason {
+    pub fn message(self) -> Option<&'static str> {
+        match self {
+            PauseReason::Manual => None,
+            PauseReason::FocusLost => Some("Window lost focus"),
+            PauseReason::PageHidden => Some("Game was hidden"),
+            PauseReason::ControllerDisconnected(_) => {
+                Some("Controller disconnected, reconnect it or continue with another device")
+            }
+        }
+    }
+}
+
+pub struct AutoPausePlugin;
+
+impl Plugin for AutoPausePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PauseReason>()
+            .add_systems(Startup, watch_page_visibility)
+            .add_systems(OnEnter(GameState::Playing), reset_pause_reason)
+            //before the state transition, so a run coming back from a hidden
+            //page is paused before it moves again
+            .add_systems(PreUpdate, auto_pause.after(InputSystem))
+            .add_systems(Update, wait_for_controller);
+    }
+}
+
+fn reset_pause_reason(mut pause_reason: ResMut<PauseReason>) {
+    *pause_reason = PauseReason::Manual;
+}
+
+//Set when the page gets hidden. Browsers throttle or stop the frames of a
+//hidden page, so it is only read once the page is shown again.
+static PAGE_WAS_HIDDEN: AtomicBool = AtomicBool::new(false);
+
+#[cfg(not(target_family = "wasm"))]
+fn watch_page_visibility() {}
+
+#[cfg(target_family = "wasm")]
+fn watch_page_visibility() {
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::JsCast;
+
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    let listener_document = document.clone();
+    let listener = Closure::<dyn FnMut()>::new(move || {
+        if listener_document.hidden() {
+            PAGE_WAS_HIDDEN.store(true, Ordering::Relaxed);
+        }
+    });
+    if document
+        .add_event_listener_with_callback("visibilitychange", listener.as_ref().unchecked_ref())
+        .is_err()
+    {
+        warn!("Could not listen to page visibility changes");
+    }
+    //the listener stays for the lifetime of the page
+    listener.forget();
+}
+
+//Events and the hidden page are read in every state, so one that happened
+//before a run started does not pause it right away.
+fn auto_pause(
+    game_state: Res<State<GameState>>,
+    mut focus_events: EventReader<WindowFocused>,
+    mut disconnected_events: EventReader<ActiveGamepadDisconnected>,
+    mut pause_reason: ResMut<PauseReason>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let focus_lost = focus_events.read().any(|event| !event.focused);
+    let disconnected = disconnected_events.read().last().map(|event| event.0);
+    let page_hidden = PAGE_WAS_HIDDEN.swap(false, Ordering::Relaxed);
+    if *game_state.get() != GameState::Playing {
+        return;
+    }
+
+    let reason = if let Some(gamepad) = disconnected {
+        PauseReason::ControllerDisconnected(gamepad)
+    } else if page_hidden {
+        PauseReason::PageHidden
+    } else if focus_lost {
+        PauseReason::FocusLost
+    } else {
+        return;
+    };
+    *pause_reason = reason;
+    next_state.set(GameState::Pause);
+}
+
+//the reconnected gamepad is announced by a toast, the pause menu goes back to
+//normal and waits for the player to continue
+fn wait_for_controller(
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    mut pause_reason: ResMut<PauseReason>,
+) {
+    for event in connection_events.read() {
+        if event.connected() && *pause_reason == PauseReason::ControllerDisconnected(event.gamepad)
+        {
+            *pause_reason = PauseReason::Manual;
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use bevy::prelude::*;
+
+use super::gamestate::GameState;
+use super::settings::Settings;
+use crate::input::{action_just_pressed, Action};
+use crate::ui::prompts::action_prompt;
+
+//Parts of `Playing` in which the world is shown but does not move yet.
+//It is a resource rather than a state, so it takes effect in the same frame
+//the game is resumed and no physics step slips through.
+#[derive(Debug, Resource, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PlayPhase {
+    #[default]
+    Running,
+    Countdown,
+    //a new run waits for the first flap
+    Ready,
+}
+
+//Systems that move the world. They only run while playing and not counting down.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SystemSet)]
+pub struct GameplaySet;
+
+#[derive(Debug, Resource)]
+struct ResumeCountdown(Timer);
+
+#[derive(Debug, Component)]
+struct CountdownOverlay;
+
+#[derive(Debug, Component)]
+struct ReadyPrompt;
+
+pub struct PlayPhasePlugin;
+
+impl Plugin for PlayPhasePlugin {
+    fn build(&self, app: &mut App) {
+        let gameplay_running =
+            || in_state(GameState::Playing).and_then(resource_equals(PlayPhase::Running));
+
+        app.init_resource::<PlayPhase>()
+            .configure_sets(FixedUpdate, GameplaySet.run_if(gameplay_running()))
+            .configure_sets(Update, GameplaySet.run_if(gameplay_running()))
+            .add_systems(
+                OnTransition {
+                    from: GameState::MainMenu,
+                    to: GameState::Playing,
+                },
+                start_ready_phase,
+            )
+            .add_systems(
+                OnTransition {
+                    from: GameState::GameOver,
+                    to: GameState::Playing,
+                },
+                start_ready_phase,
+            )
+            .add_systems(
+                OnTransition {
+                    from: GameState::Pause,
+                    to: GameState::Playing,
+                },
+                start_resume_countdown,
+            )
+            .add_systems(
+                Update,
+                (
+                    update_resume_countdown.run_if(resource_equals(PlayPhase::Countdown)),
+                    start_run
+                        .run_if(
+                            resource_equals(PlayPhase::Ready)
+                                .and_then(action_just_pressed(Action::Flap)),
+                        )
+                        //the first flap also lifts the pebble
+                        .before(GameplaySet),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), stop_resume_countdown);
+    }
+}
+
+fn spawn_ready_prompt(commands: &mut Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    height: Val::Percent(100.0),
+                    width: Val::Percent(100.0),
+                    align_items: AlignItems::End,
+                    justify_content: JustifyContent::Center,
+                    padding: UiRect::bottom(Val::Px(150.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            ReadyPrompt,
+            Name::new("ReadyPrompt"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(15.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(action_prompt(vec![Action::Flap]));
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "to start",
+                            TextStyle {
+                                font_size: 40.0,
+                                ..default()
+                            },
+                        ),
+                        Name::new("ReadyPromptText"),
+                    ));
+                });
+        });
+}
+
+fn start_ready_phase(mut commands: Commands, mut play_phase: ResMut<PlayPhase>) {
+    *play_phase = PlayPhase::Ready;
+    spawn_ready_prompt(&mut commands);
+}
+
+fn start_run(
+    mut commands: Commands,
+    mut play_phase: ResMut<PlayPhase>,
+    query_prompt: Query<Entity, With<ReadyPrompt>>,
+) {
+    *play_phase = PlayPhase::Running;
+    for prompt in query_prompt.iter() {
+        commands.entity(prompt).despawn_recursive();
+    }
+}
+
+fn start_resume_countdown(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut play_phase: ResMut<PlayPhase>,
+) {
+    //pausing before the first flap returns to waiting for it
+    if *play_phase == PlayPhase::Ready {
+        spawn_ready_prompt(&mut commands);
+        return;
+    }
+
+    let seconds = settings.resume_countdown.seconds();
+    if seconds <= 0.0 {
+        return;
+    }
+
+    *play_phase = PlayPhase::Countdown;
+    commands.insert_resource(ResumeCountdown(Timer::from_seconds(
+        seconds,
+        TimerMode::Once,
+    )));
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    height: Val::Percent(100.0),
+                    width: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                z_index: ZIndex::Global(1),
+                ..default()
+            },
+            CountdownOverlay,
+            Name::new("CountdownOverlay"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    format!("{}", seconds.ceil()),
+                    TextStyle {
+                        font_size: 150.0,
+                        ..default()
+                    },
+                ),
+                Name::new("CountdownText"),
+            ));
+        });
+}
+
+fn update_resume_countdown(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut countdown: ResMut<ResumeCountdown>,
+    mut play_phase: ResMut<PlayPhase>,
+    query_overlay: Query<(Entity, &Children), With<CountdownOverlay>>,
+    mut query_text: Query<&mut Text>,
+) {
+    countdown.0.tick(time.delta());
+    if countdown.0.finished() {
+        *play_phase = PlayPhase::Running;
+        for (overlay, _) in query_overlay.iter() {
+            commands.entity(overlay).despawn_recursive();
+        }
+        return;
+    }
+
+    let remaining = countdown.0.remaining_secs().ceil();
+    for (_, children) in query_overlay.iter() {
+        let mut texts = query_text.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            let section = text.sections.first_mut().expect("to have a TextSection");
+            section.value = format!("{remaining}");
+        }
+    }
+}
+
+//pausing again during the countdown starts it over on the next resume
+fn stop_resume_countdown(
+    mut commands: Commands,
+    mut play_phase: ResMut<PlayPhase>,
+    query_overlay: Query<Entity, With<CountdownOverlay>>,
+    query_prompt: Query<Entity, With<ReadyPrompt>>,
+) {
+    if *play_phase == PlayPhase::Countdown {
+        *play_phase = PlayPhase::Running;
+    }
+    for overlay in query_overlay.iter().chain(query_prompt.iter()) {
+        commands.entity(overlay).despawn_recursive();
+    }
+}
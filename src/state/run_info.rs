@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use super::gamestate::GameState;
+use super::settings::{Difficulty, GameMode, Settings};
+
+pub type Seed = u32;
+
+//What a run was played with. Everything random in a run comes from `rng`,
+//so the seed describes the moai layout that was played.
+#[derive(Debug, Resource)]
+pub struct RunInfo {
+    seed: Seed,
+    difficulty: Difficulty,
+    game_mode: GameMode,
+    bonus_scoring: bool,
+    rng: StdRng,
+}
+
+impl RunInfo {
+    fn new(seed: Seed, difficulty: Difficulty, game_mode: GameMode, bonus_scoring: bool) -> Self {
+        RunInfo {
+            seed,
+            difficulty,
+            game_mode,
+            bonus_scoring,
+            rng: StdRng::seed_from_u64(u64::from(seed)),
+        }
+    }
+    pub fn seed(&self) -> Seed {
+        self.seed
+    }
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+    pub fn game_mode(&self) -> GameMode {
+        self.game_mode
+    }
+    pub fn bonus_scoring(&self) -> bool {
+        self.bonus_scoring
+    }
+    //Bonus points make a score unfair against plain runs, so those runs stay
+    //out of the high score, leaderboard, score statistics and medals.
+    pub fn is_ranked(&self) -> bool {
+        !self.bonus_scoring
+    }
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+}
+
+impl Default for RunInfo {
+    fn default() -> Self {
+        RunInfo::new(0, Difficulty::default(), GameMode::default(), false)
+    }
+}
+
+pub struct RunInfoPlugin;
+
+impl Plugin for RunInfoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunInfo>()
+            .add_systems(
+                OnTransition {
+                    from: GameState::MainMenu,
+                    to: GameState::Playing,
+                },
+                setup_run,
+            )
+            .add_systems(
+                OnTransition {
+                    from: GameState::GameOver,
+                    to: GameState::Playing,
+                },
+                setup_run,
+            );
+    }
+}
+
+//difficulty and scoring changes made while paused only apply from the next run
+pub fn setup_run(settings: Res<Settings>, mut run_info: ResMut<RunInfo>) {
+    //bonus points only make sense when scoring points per moai
+    let bonus_scoring = settings.bonus_scoring && settings.game_mode == GameMode::Classic;
+    *run_info = RunInfo::new(
+        rand::random(),
+        settings.difficulty,
+        settings.game_mode,
+        bonus_scoring,
+    );
+}
@@ -0,0 +1,419 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::achievements::AchievementProgress;
+use super::gamescore::Score;
+use super::leaderboard::Leaderboard;
+use super::settings::GameMode;
+use super::statistics::Statistics;
+use crate::consts::{DEFAULT_PLAYER_NAME, LEGACY_HIGHSCORE_PATH, SAVE_PATH};
+use crate::storage::GameStorage;
+
+//First line of a save file: "<MAGIC> <version> <checksum of the rest>".
+//Version 0 is the old highscore without a header, see `parse_legacy_highscore`.
+const MAGIC: &str = "FLAPPY-PEBBLE-SAVE";
+const SAVE_VERSION: u32 = 3;
+
+pub struct SaveFilePlugin;
+
+impl Plugin for SaveFilePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveData>()
+            .add_event::<SaveError>()
+            .add_systems(Startup, load_save)
+            .add_systems(
+                Update,
+                (
+                    persist_save
+                        .run_if(resource_changed::<SaveData>())
+                        .run_if(not(resource_added::<SaveData>())),
+                    report_failed_writes,
+                ),
+            );
+    }
+}
+
+#[derive(Debug, Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SaveData {
+    high_scores: BTreeMap<GameMode, Score>,
+    leaderboards: BTreeMap<GameMode, Leaderboard>,
+    statistics: Statistics,
+    achievements: AchievementProgress,
+    //the name entered last, offered again for the next run
+    player_name: String,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        SaveData {
+            high_scores: BTreeMap::new(),
+            leaderboards: BTreeMap::new(),
+            statistics: Statistics::default(),
+            achievements: AchievementProgress::default(),
+            player_name: DEFAULT_PLAYER_NAME.to_string(),
+        }
+    }
+}
+
+impl SaveData {
+    pub fn high_score(&self, game_mode: GameMode) -> Score {
+        self.high_scores
+            .get(&game_mode)
+            .copied()
+            .unwrap_or_default()
+    }
+    pub fn set_high_score(&mut self, game_mode: GameMode, high_score: Score) {
+        self.high_scores.insert(game_mode, high_score);
+    }
+    pub fn leaderboard(&self, game_mode: GameMode) -> &Leaderboard {
+        self.leaderboards
+            .get(&game_mode)
+            .unwrap_or(&EMPTY_LEADERBOARD)
+    }
+    pub fn leaderboard_mut(&mut self, game_mode: GameMode) -> &mut Leaderboard {
+        self.leaderboards.entry(game_mode).or_default()
+    }
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+    pub fn statistics_mut(&mut self) -> &mut Statistics {
+        &mut self.statistics
+    }
+    pub fn achievements(&self) -> &AchievementProgress {
+        &self.achievements
+    }
+    pub fn achievements_mut(&mut self) -> &mut AchievementProgress {
+        &mut self.achievements
+    }
+    pub fn player_name(&self) -> &str {
+        &self.player_name
+    }
+    pub fn set_player_name(&mut self, player_name: String) {
+        self.player_name = player_name;
+    }
+}
+
+//Shown to the player when the save file could not be read or written, so a
+//reset or lost high score does not go unnoticed.
+#[derive(Debug, Event)]
+pub struct SaveError(pub String);
+
+//FNV-1a, enough to notice truncated or hand-edited files
+fn checksum(body: &str) -> u64 {
+    body.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn encode(save_data: &SaveData) -> Result<String, String> {
+    let body =
+        ron::ser::to_string_pretty(save_data, default()).map_err(|error| error.to_string())?;
+    Ok(format!(
+        "{MAGIC} {SAVE_VERSION} {:016x}\n{body}",
+        checksum(&body)
+    ))
+}
+
+//returns the save data and the version it was stored with
+fn decode(contents: &[u8]) -> Result<(SaveData, u32), String> {
+    let contents = std::str::from_utf8(contents).map_err(|_| "not a text file".to_string())?;
+    let (header, body) = contents
+        .split_once('\n')
+        .ok_or_else(|| "missing header".to_string())?;
+    let mut header = header.split(' ');
+    if header.next() != Some(MAGIC) {
+        return Err("missing magic header".to_string());
+    }
+    let version: u32 = header
+        .next()
+        .and_then(|version| version.parse().ok())
+        .ok_or_else(|| "missing version".to_string())?;
+    let stored_checksum = header
+        .next()
+        .and_then(|checksum| u64::from_str_radix(checksum, 16).ok())
+        .ok_or_else(|| "missing checksum".to_string())?;
+    if stored_checksum != checksum(body) {
+        return Err("checksum mismatch".to_string());
+    }
+    migrate(version, body).map(|save_data| (save_data, version))
+}
+
+static EMPTY_LEADERBOARD: Leaderboard = Leaderboard::new();
+
+//the parts of versions 1 and 2 that version 3 split by game mode
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SingleModeSave {
+    high_score: Score,
+    leaderboard: Leaderboard,
+}
+
+//every stored version has to be readable into the current SaveData
+fn migrate(version: u32, body: &str) -> Result<SaveData, String> {
+    match version {
+        //version 2 added the leaderboard, older saves start with an empty one
+        1 | 2 => {
+            let mut save_data: SaveData = ron::from_str(body).map_err(|error| error.to_string())?;
+            let single_mode: SingleModeSave =
+                ron::from_str(body).map_err(|error| error.to_string())?;
+            //everything was played in the classic mode before there were others
+            save_data.set_high_score(GameMode::Classic, single_mode.high_score);
+            *save_data.leaderboard_mut(GameMode::Classic) = single_mode.leaderboard;
+            Ok(save_data)
+        }
+        3 => ron::from_str(body).map_err(|error| error.to_string()),
+        version => Err(format!("unknown save version {version}")),
+    }
+}
+
+//Native builds wrote the highscore as four big-endian bytes, web builds as
+//JSON text. Four raw bytes that are all digits would be a score above 800
+//million, so the two can't be confused.
+fn parse_legacy_highscore(contents: &[u8]) -> Option<Score> {
+    std::str::from_utf8(contents)
+        .ok()
+        .and_then(|text| text.trim().parse().ok())
+        .or_else(|| <[u8; 4]>::try_from(contents).ok().map(Score::from_be_bytes))
+}
+
+fn backup_path() -> String {
+    format!("{SAVE_PATH}.bak")
+}
+
+//The save file of the previous session is kept as a backup, so it is written
+//once on start-up and not on every save.
+fn load_save(
+    storage: Res<GameStorage>,
+    mut save_data: ResMut<SaveData>,
+    mut error_events: EventWriter<SaveError>,
+) {
+    let contents = match storage.read(SAVE_PATH) {
+        Ok(Some(contents)) => contents,
+        Ok(None) => {
+            let legacy = storage.read(LEGACY_HIGHSCORE_PATH).ok().flatten();
+            if let Some(high_score) = legacy.as_deref().and_then(parse_legacy_highscore) {
+                info!("Migrating save file from version 0 to {SAVE_VERSION}");
+                save_data.set_high_score(GameMode::Classic, high_score);
+                write_save(&storage, &save_data);
+            }
+            return;
+        }
+        Err(error) => {
+            warn!("Could not read save file: {error}");
+            error_events.send(SaveError("Could not read the save file".to_string()));
+            return;
+        }
+    };
+
+    match decode(&contents) {
+        Ok((loaded, version)) => {
+            *save_data = loaded;
+            storage
+                .write(&backup_path(), &contents)
+                .unwrap_or_else(|error| warn!("Could not back up save file: {error}"));
+            if version < SAVE_VERSION {
+                info!("Migrating save file from version {version} to {SAVE_VERSION}");
+                write_save(&storage, &save_data);
+            }
+        }
+        Err(error) => {
+            warn!("Save file is corrupt: {error}");
+            //kept for inspection instead of being overwritten
+            storage
+                .write(&format!("{SAVE_PATH}.corrupt"), &contents)
+                .and_then(|_| storage.remove(SAVE_PATH))
+                .unwrap_or_else(|error| warn!("Could not move corrupt save file: {error}"));
+            let backup = storage
+                .read(&backup_path())
+                .ok()
+                .flatten()
+                .and_then(|contents| decode(&contents).ok());
+            match backup {
+                Some((loaded, _)) => {
+                    *save_data = loaded;
+                    error_events.send(SaveError(
+                        "The save file was damaged and has been restored from a backup".to_string(),
+                    ));
+                    write_save(&storage, &save_data);
+                }
+                None => {
+                    error_events.send(SaveError(
+                        "The save file was damaged, the high score has been reset".to_string(),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn persist_save(storage: Res<GameStorage>, save_data: Res<SaveData>) {
+    write_save(&storage, &save_data);
+}
+
+fn report_failed_writes(storage: Res<GameStorage>, mut error_events: EventWriter<SaveError>) {
+    if storage.take_failed_write(SAVE_PATH) {
+        error_events.send(SaveError("Could not write the save file".to_string()));
+    }
+}
+
+fn write_save(storage: &GameStorage, save_data: &SaveData) {
+    match encode(save_data) {
+        Ok(contents) => storage.write_in_background(SAVE_PATH, contents.into_bytes()),
+        Err(error) => warn!("Could not encode save file: {error}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use bevy::tasks::{IoTaskPool, TaskPool};
+
+    use super::*;
+    use crate::state::settings::Difficulty;
+    use crate::storage::MemoryStorage;
+
+    fn with_header(version: u32, body: &str) -> Vec<u8> {
+        format!("{MAGIC} {version} {:016x}\n{body}", checksum(body)).into_bytes()
+    }
+
+    //runs `load_save` and returns what it loaded and the errors it reported
+    fn load(storage: &GameStorage) -> (SaveData, Vec<String>) {
+        IoTaskPool::get_or_init(TaskPool::new);
+        let mut app = App::new();
+        app.insert_resource(storage.clone())
+            .init_resource::<SaveData>()
+            .add_event::<SaveError>()
+            .add_systems(Update, load_save);
+        app.update();
+        let errors = app
+            .world
+            .resource::<Events<SaveError>>()
+            .iter_current_update_events()
+            .map(|error| error.0.clone())
+            .collect();
+        (app.world.resource::<SaveData>().clone(), errors)
+    }
+
+    //the migrated save is written in the background
+    fn wait_for_save(storage: &GameStorage) -> SaveData {
+        let start = Instant::now();
+        loop {
+            if let Some(contents) = storage.read(SAVE_PATH).unwrap() {
+                return decode(&contents).unwrap().0;
+            }
+            assert!(start.elapsed() < Duration::from_secs(5), "save not written");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn encoded_save_decodes_to_the_same_data() {
+        let mut save_data = SaveData::default();
+        save_data.set_high_score(GameMode::Distance, 42);
+        save_data.set_player_name("PEBBLE".to_string());
+
+        let contents = encode(&save_data).unwrap();
+        assert_eq!(decode(contents.as_bytes()), Ok((save_data, SAVE_VERSION)));
+    }
+
+    #[test]
+    fn changed_body_fails_the_checksum() {
+        let mut save_data = SaveData::default();
+        save_data.set_high_score(GameMode::Classic, 7);
+        let contents = encode(&save_data).unwrap();
+        let (header, body) = contents.split_once('\n').unwrap();
+        let contents = format!("{header}\n{}", body.replace("7", "8"));
+
+        assert_eq!(
+            decode(contents.as_bytes()),
+            Err("checksum mismatch".to_string())
+        );
+    }
+
+    #[test]
+    fn single_mode_saves_migrate_into_classic() {
+        let version_1 = "(high_score: 12, player_name: \"OLD\")";
+        let version_2 = "(
+            high_score: 12,
+            leaderboard: ([(
+                name: \"OLD\",
+                score: 12,
+                timestamp: 0,
+                difficulty: Hard,
+                seed: 3,
+            )]),
+        )";
+
+        let (save_data, version) = decode(&with_header(1, version_1)).unwrap();
+        assert_eq!(version, 1);
+        assert_eq!(save_data.high_score(GameMode::Classic), 12);
+        assert_eq!(save_data.player_name(), "OLD");
+        assert!(save_data
+            .leaderboard(GameMode::Classic)
+            .entries()
+            .is_empty());
+
+        let (save_data, version) = decode(&with_header(2, version_2)).unwrap();
+        assert_eq!(version, 2);
+        assert_eq!(save_data.high_score(GameMode::Classic), 12);
+        assert_eq!(save_data.high_score(GameMode::Distance), 0);
+        let entries = save_data.leaderboard(GameMode::Classic).entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].score(), 12);
+        assert_eq!(entries[0].difficulty(), Difficulty::Hard);
+    }
+
+    #[test]
+    fn unknown_version_is_rejected() {
+        assert_eq!(
+            decode(&with_header(SAVE_VERSION + 1, "()")),
+            Err(format!("unknown save version {}", SAVE_VERSION + 1))
+        );
+    }
+
+    #[test]
+    fn legacy_highscores_are_read_in_both_formats() {
+        assert_eq!(parse_legacy_highscore(&[0, 0, 1, 2]), Some(258));
+        assert_eq!(parse_legacy_highscore(b"258"), Some(258));
+        assert_eq!(parse_legacy_highscore(b"258\n"), Some(258));
+        assert_eq!(parse_legacy_highscore(b"1234"), Some(1234));
+        assert_eq!(parse_legacy_highscore(&[1, 2, 3]), None);
+    }
+
+    #[test]
+    fn corrupt_save_is_restored_from_the_backup() {
+        let storage = GameStorage::new(MemoryStorage::default());
+        let mut backup = SaveData::default();
+        backup.set_high_score(GameMode::Classic, 30);
+        let backup = encode(&backup).unwrap();
+        storage.write(&backup_path(), backup.as_bytes()).unwrap();
+        storage.write(SAVE_PATH, b"not a save file").unwrap();
+
+        let (save_data, errors) = load(&storage);
+        assert_eq!(save_data.high_score(GameMode::Classic), 30);
+        assert_eq!(
+            errors,
+            ["The save file was damaged and has been restored from a backup"]
+        );
+        assert_eq!(
+            storage.read(&format!("{SAVE_PATH}.corrupt")),
+            Ok(Some(b"not a save file".to_vec()))
+        );
+        assert_eq!(wait_for_save(&storage), save_data);
+    }
+
+    #[test]
+    fn missing_save_is_migrated_from_the_legacy_highscore() {
+        let storage = GameStorage::new(MemoryStorage::default());
+        storage.write(LEGACY_HIGHSCORE_PATH, &[0, 0, 0, 9]).unwrap();
+
+        let (save_data, errors) = load(&storage);
+        assert_eq!(save_data.high_score(GameMode::Classic), 9);
+        assert!(errors.is_empty());
+        assert_eq!(wait_for_save(&storage), save_data);
+    }
+}
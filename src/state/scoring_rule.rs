@@ -0,0 +1,145 @@
+use std::fmt::Debug;
+
+use super::gamescore::Score;
+use crate::consts;
+
+//What happened in the world during one fixed step. Only updated while the
+//world moves.
+#[derive(Debug)]
+pub struct ScoringContext {
+    pub delta_seconds: f32,
+    //how far the world scrolled past the pebble
+    pub distance: f32,
+    //moai whose middle went past the pebble
+    pub moai_passed: u32,
+}
+
+//Decides how a run is scored. Each game mode uses its own rule, a new one is
+//made for every run so rules may keep their own progress.
+pub trait ScoringRule: Debug + Send + Sync {
+    //what the score measures, e.g. "Distance"
+    fn label(&self) -> &'static str;
+    //shown after the score, empty for plain points
+    fn units(&self) -> &'static str;
+    //points earned since the last update
+    fn update(&mut self, context: &ScoringContext) -> Score;
+
+    fn format_score(&self, score: Score) -> String {
+        match self.units() {
+            "" => score.to_string(),
+            units => format!("{score} {units}"),
+        }
+    }
+}
+
+//a point for every moai passed
+#[derive(Debug, Default)]
+pub struct PerMoai;
+
+impl ScoringRule for PerMoai {
+    fn label(&self) -> &'static str {
+        "Score"
+    }
+    fn units(&self) -> &'static str {
+        ""
+    }
+    fn update(&mut self, context: &ScoringContext) -> Score {
+        context.moai_passed
+    }
+}
+
+//a point for every metre, see `consts::PIXELS_PER_METRE`
+#[derive(Debug, Default)]
+pub struct DistanceTravelled {
+    //f64, so long runs don't lose the small steps
+    metres: f64,
+}
+
+impl ScoringRule for DistanceTravelled {
+    fn label(&self) -> &'static str {
+        "Distance"
+    }
+    fn units(&self) -> &'static str {
+        "m"
+    }
+    fn update(&mut self, context: &ScoringContext) -> Score {
+        let before = self.metres;
+        self.metres += f64::from(context.distance / consts::PIXELS_PER_METRE);
+        self.metres as Score - before as Score
+    }
+}
+
+//a point for every second the world moved
+#[derive(Debug, Default)]
+pub struct TimeSurvived {
+    seconds: f64,
+}
+
+impl ScoringRule for TimeSurvived {
+    fn label(&self) -> &'static str {
+        "Time"
+    }
+    fn units(&self) -> &'static str {
+        "s"
+    }
+    fn update(&mut self, context: &ScoringContext) -> Score {
+        let before = self.seconds;
+        self.seconds += f64::from(context.delta_seconds);
+        self.seconds as Score - before as Score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP_SECONDS: f32 = 1.0 / 64.0;
+    const SPEED: f32 = 2.0 * consts::PIXELS_PER_METRE;
+
+    fn step(moai_passed: u32) -> ScoringContext {
+        ScoringContext {
+            delta_seconds: STEP_SECONDS,
+            distance: SPEED * STEP_SECONDS,
+            moai_passed,
+        }
+    }
+
+    //total points over the given number of steps
+    fn run(rule: &mut dyn ScoringRule, steps: u32) -> Score {
+        (0..steps).map(|_| rule.update(&step(0))).sum()
+    }
+
+    #[test]
+    fn per_moai_scores_each_moai_passed() {
+        let mut rule = PerMoai;
+        assert_eq!(rule.update(&step(0)), 0);
+        assert_eq!(rule.update(&step(1)), 1);
+        assert_eq!(rule.update(&step(2)), 2);
+        assert_eq!(rule.format_score(3), "3");
+    }
+
+    #[test]
+    fn distance_scores_whole_metres() {
+        let mut rule = DistanceTravelled::default();
+        //two metres a second, half a second is one metre
+        assert_eq!(run(&mut rule, 31), 0);
+        assert_eq!(run(&mut rule, 1), 1);
+        assert_eq!(run(&mut rule, 64 * 60), 2 * 60);
+        assert_eq!(rule.format_score(12), "12 m");
+    }
+
+    #[test]
+    fn time_scores_whole_seconds() {
+        let mut rule = TimeSurvived::default();
+        assert_eq!(run(&mut rule, 63), 0);
+        assert_eq!(run(&mut rule, 1), 1);
+        assert_eq!(run(&mut rule, 64 * 60 * 60), 60 * 60);
+        assert_eq!(rule.format_score(5), "5 s");
+    }
+
+    #[test]
+    fn distance_and_time_ignore_moai() {
+        assert_eq!(DistanceTravelled::default().update(&step(3)), 0);
+        assert_eq!(TimeSurvived::default().update(&step(3)), 0);
+    }
+}
@@ -0,0 +1,257 @@
+use bevy::prelude::*;
+use bevy::window::{PresentMode, WindowMode};
+use serde::{Deserialize, Serialize};
+
+use super::gamestate::GameState;
+use super::scoring_rule::{DistanceTravelled, PerMoai, ScoringRule, TimeSurvived};
+use crate::consts::{self, SETTINGS_PATH};
+use crate::input::ActionMap;
+use crate::storage::GameStorage;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    pub fn next(self) -> DisplayMode {
+        match self {
+            DisplayMode::Windowed => DisplayMode::Borderless,
+            DisplayMode::Borderless => DisplayMode::Fullscreen,
+            DisplayMode::Fullscreen => DisplayMode::Windowed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn next(self) -> Difficulty {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    //vertical space between the lower and the upper moai
+    pub fn moai_gap(self) -> f32 {
+        let scale = match self {
+            Difficulty::Easy => 1.2,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.8,
+        };
+        consts::MOAI_VERTICAL_DISTANCE * scale
+    }
+}
+
+//Game modes only differ in how runs are scored. Scores of different modes
+//can't be compared, so high scores and leaderboards are kept per mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub enum GameMode {
+    #[default]
+    Classic,
+    Distance,
+    Survival,
+}
+
+impl GameMode {
+    pub fn next(self) -> GameMode {
+        match self {
+            GameMode::Classic => GameMode::Distance,
+            GameMode::Distance => GameMode::Survival,
+            GameMode::Survival => GameMode::Classic,
+        }
+    }
+
+    pub fn scoring_rule(self) -> Box<dyn ScoringRule> {
+        match self {
+            GameMode::Classic => Box::new(PerMoai),
+            GameMode::Distance => Box::<DistanceTravelled>::default(),
+            GameMode::Survival => Box::<TimeSurvived>::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CountdownLength {
+    Off,
+    OneSecond,
+    #[default]
+    ThreeSeconds,
+}
+
+impl CountdownLength {
+    pub fn next(self) -> CountdownLength {
+        match self {
+            CountdownLength::Off => CountdownLength::OneSecond,
+            CountdownLength::OneSecond => CountdownLength::ThreeSeconds,
+            CountdownLength::ThreeSeconds => CountdownLength::Off,
+        }
+    }
+
+    pub fn seconds(self) -> f32 {
+        match self {
+            CountdownLength::Off => 0.0,
+            CountdownLength::OneSecond => 1.0,
+            CountdownLength::ThreeSeconds => 3.0,
+        }
+    }
+}
+
+#[derive(Debug, Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub display_mode: DisplayMode,
+    pub vsync: bool,
+    pub show_fps: bool,
+    pub reduced_motion: bool,
+    //used from the next run on
+    pub difficulty: Difficulty,
+    pub game_mode: GameMode,
+    //extra points for near misses and centred passes, from the next run on
+    pub bonus_scoring: bool,
+    //countdown after unpausing before the world moves again
+    pub resume_countdown: CountdownLength,
+    //gamepad rumble switch and per event intensities, 0 turns one off
+    pub rumble: bool,
+    pub flap_rumble: f32,
+    pub score_rumble: f32,
+    pub death_rumble: f32,
+    pub controls: ActionMap,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            master_volume: 1.0,
+            music_volume: 0.8,
+            sfx_volume: 1.0,
+            display_mode: DisplayMode::Windowed,
+            vsync: true,
+            show_fps: false,
+            reduced_motion: false,
+            difficulty: Difficulty::Normal,
+            game_mode: GameMode::Classic,
+            bonus_scoring: false,
+            resume_countdown: CountdownLength::ThreeSeconds,
+            rumble: true,
+            flap_rumble: 0.0,
+            score_rumble: 0.4,
+            death_rumble: 1.0,
+            controls: ActionMap::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn music_volume(&self) -> f32 {
+        self.master_volume * self.music_volume
+    }
+
+    pub fn sfx_volume(&self) -> f32 {
+        self.master_volume * self.sfx_volume
+    }
+}
+
+//The settings screen can be opened both from the main menu and the pause menu,
+//so remember where to go back to.
+#[derive(Debug, Resource, Default)]
+pub struct SettingsOrigin(pub GameState);
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Settings>()
+            .init_resource::<SettingsOrigin>()
+            .add_systems(Startup, load_settings)
+            .add_systems(
+                OnTransition {
+                    from: GameState::MainMenu,
+                    to: GameState::Settings,
+                },
+                set_settings_origin(GameState::MainMenu),
+            )
+            .add_systems(
+                OnTransition {
+                    from: GameState::Pause,
+                    to: GameState::Settings,
+                },
+                set_settings_origin(GameState::Pause),
+            )
+            .add_systems(
+                Update,
+                (
+                    apply_settings,
+                    persist_settings.run_if(not(resource_added::<Settings>())),
+                )
+                    .run_if(resource_changed::<Settings>()),
+            );
+    }
+}
+
+fn set_settings_origin(origin: GameState) -> impl FnMut(ResMut<SettingsOrigin>) {
+    move |mut settings_origin: ResMut<SettingsOrigin>| settings_origin.0 = origin
+}
+
+fn apply_settings(settings: Res<Settings>, mut window: Query<&mut Window>) {
+    let Ok(mut window) = window.get_single_mut() else {
+        return;
+    };
+
+    if cfg!(not(target_family = "wasm")) {
+        let mode = match settings.display_mode {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+            DisplayMode::Fullscreen => WindowMode::Fullscreen,
+        };
+        if window.mode != mode {
+            window.mode = mode;
+        }
+    }
+
+    let present_mode = if settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+//Changes while a write is in flight are merged into one, e.g. when dragging a
+//volume slider, see `GameStorage::write_in_background`.
+fn persist_settings(storage: Res<GameStorage>, settings: Res<Settings>) {
+    match ron::ser::to_string_pretty(&*settings, default()) {
+        Ok(serialized) => storage.write_in_background(SETTINGS_PATH, serialized.into_bytes()),
+        Err(error) => warn!("Could not write settings: {error}"),
+    }
+}
+
+fn load_settings(storage: Res<GameStorage>, mut settings: ResMut<Settings>) {
+    let read_settings = storage.read(SETTINGS_PATH).and_then(|contents| {
+        let Some(contents) = contents else {
+            return Ok(Settings::default());
+        };
+        let serialized = String::from_utf8(contents).map_err(|error| error.to_string())?;
+        ron::from_str(&serialized).map_err(|error| error.to_string())
+    });
+    *settings = read_settings.unwrap_or_else(|error| {
+        warn!("Could not read settings: {error}");
+        Settings::default()
+    });
+}
@@ -0,0 +1,319 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::bonus_scoring::BonusKind;
+use super::gamescore::{GameScore, Score};
+use super::gamestate::GameState;
+use super::play_phase::GameplaySet;
+use super::run_info::{setup_run, RunInfo};
+use super::save_file::SaveData;
+use super::settings::GameMode;
+use crate::screen_entity::{DeathCause, MoaiCleared, MoaiPassed, PebbleDied, PebbleFlapped};
+
+pub struct StatisticsPlugin;
+
+impl Plugin for StatisticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStatistics>()
+            .init_resource::<SessionStatistics>()
+            .add_systems(
+                OnTransition {
+                    from: GameState::MainMenu,
+                    to: GameState::Playing,
+                },
+                reset_run_statistics.after(setup_run),
+            )
+            .add_systems(
+                OnTransition {
+                    from: GameState::GameOver,
+                    to: GameState::Playing,
+                },
+                reset_run_statistics.after(setup_run),
+            )
+            .add_systems(
+                Update,
+                (
+                    count_run_time.in_set(GameplaySet),
+                    (
+                        count_flaps,
+                        count_moai_passed,
+                        count_moai_cleared,
+                        record_statistics,
+                    )
+                        .chain()
+                        .run_if(in_state(GameState::Playing)),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Statistics {
+    games: u32,
+    flaps: u64,
+    moai_passed: u64,
+    seconds_played: f64,
+    longest_run_seconds: f32,
+    //how many ranked classic runs ended with each score, enough for the
+    //average and the median. Distances and times of the other modes, or bonus
+    //points, would skew them.
+    scores: BTreeMap<Score, u32>,
+    deaths: BTreeMap<DeathCause, u32>,
+}
+
+impl Statistics {
+    pub fn games(&self) -> u32 {
+        self.games
+    }
+    pub fn flaps(&self) -> u64 {
+        self.flaps
+    }
+    pub fn moai_passed(&self) -> u64 {
+        self.moai_passed
+    }
+    pub fn seconds_played(&self) -> f64 {
+        self.seconds_played
+    }
+    pub fn longest_run_seconds(&self) -> f32 {
+        self.longest_run_seconds
+    }
+    pub fn deaths(&self, cause: DeathCause) -> u32 {
+        self.deaths.get(&cause).copied().unwrap_or_default()
+    }
+
+    pub fn average_score(&self) -> Option<f32> {
+        let scored_games = self.scored_games();
+        if scored_games == 0 {
+            return None;
+        }
+        let total: u64 = self
+            .scores
+            .iter()
+            .map(|(score, count)| u64::from(*score) * u64::from(*count))
+            .sum();
+        Some(total as f32 / scored_games as f32)
+    }
+
+    pub fn median_score(&self) -> Option<f32> {
+        let scored_games = self.scored_games();
+        let middle = scored_games / 2;
+        let upper = self.nth_score(middle)?;
+        if scored_games % 2 == 1 {
+            return Some(upper as f32);
+        }
+        let lower = self.nth_score(middle - 1)?;
+        Some((lower + upper) as f32 / 2.0)
+    }
+
+    fn scored_games(&self) -> u32 {
+        self.scores.values().sum()
+    }
+
+    //the score at the index if all runs were sorted by score
+    fn nth_score(&self, index: u32) -> Option<Score> {
+        let mut seen = 0;
+        for (score, count) in self.scores.iter() {
+            seen += count;
+            if index < seen {
+                return Some(*score);
+            }
+        }
+        None
+    }
+
+    fn record(&mut self, run: &RunStatistics, score: Option<Score>, cause: DeathCause) {
+        self.games += 1;
+        self.flaps += u64::from(run.flaps);
+        self.moai_passed += u64::from(run.moai_passed);
+        self.seconds_played += f64::from(run.seconds);
+        self.longest_run_seconds = self.longest_run_seconds.max(run.seconds);
+        if let Some(score) = score {
+            *self.scores.entry(score).or_default() += 1;
+        }
+        *self.deaths.entry(cause).or_default() += 1;
+    }
+}
+
+//Statistics since the game was started. The all-time ones are in `SaveData`.
+#[derive(Debug, Resource, Default)]
+pub struct SessionStatistics(pub Statistics);
+
+//Counted during the run and added to the statistics when it ends, so the
+//save file is not written on every flap. Also shown in the run's results.
+#[derive(Debug, Resource, Default)]
+pub struct RunStatistics {
+    flaps: u32,
+    moai_passed: u32,
+    //only while the world moves, pauses and countdowns don't count
+    seconds: f32,
+    near_misses: u32,
+    //stylish passes in a row, a plain one breaks the streak like the combo
+    //of bonus scoring
+    streak: u32,
+    best_streak: u32,
+    moai_cleared: u32,
+    accuracy_sum: f32,
+}
+
+impl RunStatistics {
+    pub fn flaps(&self) -> u32 {
+        self.flaps
+    }
+    pub fn moai_passed(&self) -> u32 {
+        self.moai_passed
+    }
+    pub fn seconds(&self) -> f32 {
+        self.seconds
+    }
+    pub fn near_misses(&self) -> u32 {
+        self.near_misses
+    }
+    pub fn best_streak(&self) -> u32 {
+        self.best_streak
+    }
+
+    //how close to the middle of the gaps the pebble flew, see `MoaiCleared::accuracy`
+    pub fn average_accuracy(&self) -> Option<f32> {
+        if self.moai_cleared == 0 {
+            return None;
+        }
+        Some(self.accuracy_sum / self.moai_cleared as f32)
+    }
+
+    fn record_cleared(&mut self, cleared: &MoaiCleared) {
+        self.moai_cleared += 1;
+        self.accuracy_sum += cleared.accuracy();
+        if cleared.is_near_miss() {
+            self.near_misses += 1;
+        }
+        if BonusKind::of(cleared).is_some() {
+            self.streak += 1;
+            self.best_streak = self.best_streak.max(self.streak);
+        } else {
+            self.streak = 0;
+        }
+    }
+}
+
+fn reset_run_statistics(mut run_statistics: ResMut<RunStatistics>) {
+    *run_statistics = RunStatistics::default();
+}
+
+fn count_run_time(time: Res<Time>, mut run_statistics: ResMut<RunStatistics>) {
+    run_statistics.seconds += time.delta_seconds();
+}
+
+fn count_flaps(
+    mut flap_events: EventReader<PebbleFlapped>,
+    mut run_statistics: ResMut<RunStatistics>,
+) {
+    run_statistics.flaps += flap_events.read().count() as u32;
+}
+
+fn count_moai_passed(
+    mut passed_events: EventReader<MoaiPassed>,
+    mut run_statistics: ResMut<RunStatistics>,
+) {
+    run_statistics.moai_passed += passed_events.read().count() as u32;
+}
+
+fn count_moai_cleared(
+    mut cleared_events: EventReader<MoaiCleared>,
+    mut run_statistics: ResMut<RunStatistics>,
+) {
+    for cleared in cleared_events.read() {
+        run_statistics.record_cleared(cleared);
+    }
+}
+
+//only the first death of the run counts, the pebble may hit several things at once
+pub fn record_statistics(
+    mut death_events: EventReader<PebbleDied>,
+    game_score: Res<GameScore>,
+    run_info: Res<RunInfo>,
+    run_statistics: Res<RunStatistics>,
+    mut session_statistics: ResMut<SessionStatistics>,
+    mut save_data: ResMut<SaveData>,
+) {
+    let Some(cause) = death_events.read().next().map(|death| death.cause) else {
+        return;
+    };
+    death_events.clear();
+
+    let score = (run_info.game_mode() == GameMode::Classic && run_info.is_ranked())
+        .then(|| game_score.get_current_score());
+    session_statistics.0.record(&run_statistics, score, cause);
+    save_data
+        .statistics_mut()
+        .record(&run_statistics, score, cause);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statistics(scores: &[Score]) -> Statistics {
+        let mut statistics = Statistics::default();
+        for score in scores {
+            *statistics.scores.entry(*score).or_default() += 1;
+        }
+        statistics
+    }
+
+    #[test]
+    fn no_scores_have_no_median() {
+        assert_eq!(statistics(&[]).median_score(), None);
+    }
+
+    #[test]
+    fn odd_count_takes_the_middle_score() {
+        assert_eq!(statistics(&[7]).median_score(), Some(7.0));
+        assert_eq!(statistics(&[9, 1, 4]).median_score(), Some(4.0));
+    }
+
+    #[test]
+    fn even_count_averages_the_two_middle_scores() {
+        assert_eq!(statistics(&[1, 4]).median_score(), Some(2.5));
+        assert_eq!(statistics(&[10, 2, 3, 0]).median_score(), Some(2.5));
+    }
+
+    #[test]
+    fn repeated_scores_count_once_per_run() {
+        assert_eq!(statistics(&[3, 3, 3, 20]).median_score(), Some(3.0));
+        assert_eq!(statistics(&[1, 1, 5, 5]).median_score(), Some(3.0));
+    }
+
+    //passes through a gap that leaves at most 100 on each side
+    fn run(clearances: &[f32]) -> RunStatistics {
+        let mut run_statistics = RunStatistics::default();
+        for clearance in clearances {
+            run_statistics.record_cleared(&MoaiCleared {
+                clearance: *clearance,
+                max_clearance: 100.0,
+                gap_centre: Vec2::ZERO,
+            });
+        }
+        run_statistics
+    }
+
+    #[test]
+    fn plain_passes_have_no_streak() {
+        assert_eq!(run(&[50.0, 40.0, 60.0]).best_streak(), 0);
+    }
+
+    #[test]
+    fn near_misses_and_centre_passes_extend_the_streak() {
+        assert_eq!(run(&[5.0, 90.0, 5.0]).best_streak(), 3);
+    }
+
+    #[test]
+    fn plain_pass_breaks_the_streak() {
+        let run_statistics = run(&[5.0, 90.0, 50.0, 5.0]);
+        assert_eq!(run_statistics.best_streak(), 2);
+        assert_eq!(run_statistics.near_misses(), 2);
+    }
+}
@@ -0,0 +1,61 @@
+//seconds since the unix epoch
+pub type Timestamp = u64;
+
+#[cfg(not(target_family = "wasm"))]
+pub fn now() -> Timestamp {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+//SystemTime is not available in the browser
+#[cfg(target_family = "wasm")]
+pub fn now() -> Timestamp {
+    (js_sys::Date::now() / 1000.0) as Timestamp
+}
+
+//UTC date as YYYY-MM-DD, see http://howardhinnant.github.io/date_algorithms.html
+pub fn format_date(timestamp: Timestamp) -> String {
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: Timestamp = 86_400;
+
+    #[test]
+    fn epoch_is_the_first_of_january_1970() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(DAY - 1), "1970-01-01");
+    }
+
+    #[test]
+    fn leap_days_exist_in_leap_years() {
+        assert_eq!(format_date(1_709_164_800), "2024-02-29");
+        assert_eq!(format_date(1_709_164_800 + DAY), "2024-03-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+    }
+
+    #[test]
+    fn year_2100_is_not_a_leap_year() {
+        assert_eq!(format_date(4_107_456_000), "2100-02-28");
+        assert_eq!(format_date(4_107_456_000 + DAY), "2100-03-01");
+    }
+}
@@ -0,0 +1,70 @@
+use std::fs::{self, File};
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::SaveStorage;
+use crate::consts::{LEGACY_STORAGE_DIR, STORAGE_DIR_NAME};
+
+//numbers the temporary files, so writes at the same time never share one
+static NEXT_TEMP_FILE: AtomicU64 = AtomicU64::new(0);
+
+//One file per key in the platform data directory (`$XDG_DATA_HOME` on Linux).
+//Older versions saved into the assets folder, those files are still read
+//until the key is written again.
+pub struct FileStorage {
+    dir: PathBuf,
+    legacy_dir: PathBuf,
+}
+
+impl FileStorage {
+    pub fn in_data_dir() -> Option<FileStorage> {
+        Some(FileStorage {
+            dir: dirs::data_dir()?.join(STORAGE_DIR_NAME),
+            legacy_dir: PathBuf::from(LEGACY_STORAGE_DIR),
+        })
+    }
+}
+
+impl SaveStorage for FileStorage {
+    fn read(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        for dir in [&self.dir, &self.legacy_dir] {
+            match fs::read(dir.join(key)) {
+                Ok(contents) => return Ok(Some(contents)),
+                Err(error) if error.kind() == ErrorKind::NotFound => continue,
+                Err(error) => return Err(error.to_string()),
+            }
+        }
+        Ok(None)
+    }
+
+    //written to a temporary file first, so a crash mid-write never leaves a
+    //half written file behind
+    fn write(&self, key: &str, contents: &[u8]) -> Result<(), String> {
+        let temp_number = NEXT_TEMP_FILE.fetch_add(1, Ordering::Relaxed);
+        let temp_path = self
+            .dir
+            .join(format!("{key}.{}-{temp_number}.tmp", std::process::id()));
+        fs::create_dir_all(&self.dir)
+            .and_then(|_| File::create(&temp_path))
+            .and_then(|mut file| {
+                file.write_all(contents)?;
+                file.sync_all()
+            })
+            .and_then(|_| fs::rename(&temp_path, self.dir.join(key)))
+            .map_err(|error| {
+                let _ = fs::remove_file(&temp_path);
+                error.to_string()
+            })
+    }
+
+    fn remove(&self, key: &str) -> Result<(), String> {
+        for dir in [&self.dir, &self.legacy_dir] {
+            match fs::remove_file(dir.join(key)) {
+                Err(error) if error.kind() != ErrorKind::NotFound => return Err(error.to_string()),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use gloo_storage::Storage;
+
+use super::SaveStorage;
+
+//The browser's LocalStorage, values are stored as text.
+pub struct LocalStorage;
+
+impl SaveStorage for LocalStorage {
+    fn read(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        gloo_storage::LocalStorage::raw()
+            .get_item(key)
+            .map(|contents| contents.map(String::into_bytes))
+            .map_err(|_| "LocalStorage is not available".to_string())
+    }
+
+    //LocalStorage writes are atomic per key
+    fn write(&self, key: &str, contents: &[u8]) -> Result<(), String> {
+        let contents = std::str::from_utf8(contents).map_err(|error| error.to_string())?;
+        gloo_storage::LocalStorage::raw()
+            .set_item(key, contents)
+            .map_err(|_| format!("Could not write {key} to LocalStorage"))
+    }
+
+    fn remove(&self, key: &str) -> Result<(), String> {
+        gloo_storage::LocalStorage::raw()
+            .remove_item(key)
+            .map_err(|_| format!("Could not remove {key} from LocalStorage"))
+    }
+}
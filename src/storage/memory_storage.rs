@@ -0,0 +1,27 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::SaveStorage;
+
+//Forgets everything on exit. Used for tests and when there is nowhere to save.
+#[derive(Debug, Default)]
+pub struct MemoryStorage(Mutex<HashMap<String, Vec<u8>>>);
+
+impl SaveStorage for MemoryStorage {
+    fn read(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        let values = self.0.lock().map_err(|error| error.to_string())?;
+        Ok(values.get(key).cloned())
+    }
+
+    fn write(&self, key: &str, contents: &[u8]) -> Result<(), String> {
+        let mut values = self.0.lock().map_err(|error| error.to_string())?;
+        values.insert(key.to_string(), contents.to_vec());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), String> {
+        let mut values = self.0.lock().map_err(|error| error.to_string())?;
+        values.remove(key);
+        Ok(())
+    }
+}
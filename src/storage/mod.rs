@@ -0,0 +1,136 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use bevy::prelude::*;
+use bevy::tasks::IoTaskPool;
+
+#[cfg(not(target_family = "wasm"))]
+mod file_storage;
+#[cfg(target_family = "wasm")]
+mod local_storage;
+mod memory_storage;
+
+pub use memory_storage::MemoryStorage;
+
+//Everything that is persisted (save file, settings) goes through this, keyed
+//by a short name like `consts::SAVE_PATH`.
+pub trait SaveStorage: Send + Sync {
+    fn read(&self, key: &str) -> Result<Option<Vec<u8>>, String>;
+    //replaces the value in one step, a failed write keeps the previous value
+    fn write(&self, key: &str, contents: &[u8]) -> Result<(), String>;
+    fn remove(&self, key: &str) -> Result<(), String>;
+}
+
+//Shared so writes can be moved to the IO task pool.
+#[derive(Resource, Clone)]
+pub struct GameStorage {
+    storage: Arc<dyn SaveStorage>,
+    background_writes: Arc<Mutex<BackgroundWrites>>,
+}
+
+//Only the newest contents of each key wait to be written, and a single task
+//writes them one after the other. An older snapshot can't overwrite a newer
+//one, and quick changes in a row end up as a single write.
+#[derive(Debug, Default)]
+struct BackgroundWrites {
+    pending: BTreeMap<String, Vec<u8>>,
+    in_flight: bool,
+    failed: BTreeSet<String>,
+}
+
+impl GameStorage {
+    pub fn new(storage: impl SaveStorage + 'static) -> Self {
+        GameStorage {
+            storage: Arc::new(storage),
+            background_writes: default(),
+        }
+    }
+
+    pub fn write_in_background(&self, key: &str, contents: Vec<u8>) {
+        let mut background_writes = self.background_writes();
+        background_writes.pending.insert(key.to_string(), contents);
+        if background_writes.in_flight {
+            return;
+        }
+        background_writes.in_flight = true;
+        let storage = self.clone();
+        IoTaskPool::get()
+            .spawn(async move { storage.write_pending() })
+            .detach();
+    }
+
+    //whether a background write of the key failed since the last call
+    pub fn take_failed_write(&self, key: &str) -> bool {
+        self.background_writes().failed.remove(key)
+    }
+
+    fn write_pending(&self) {
+        loop {
+            let next = {
+                let mut background_writes = self.background_writes();
+                let next = background_writes.pending.pop_first();
+                background_writes.in_flight = next.is_some();
+                next
+            };
+            let Some((key, contents)) = next else {
+                return;
+            };
+            if let Err(error) = self.write(&key, &contents) {
+                warn!("Could not write {key}: {error}");
+                self.background_writes().failed.insert(key);
+            }
+        }
+    }
+
+    fn background_writes(&self) -> MutexGuard<'_, BackgroundWrites> {
+        self.background_writes
+            .lock()
+            .expect("to lock the background writes")
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn platform() -> Self {
+        match file_storage::FileStorage::in_data_dir() {
+            Some(storage) => GameStorage::new(storage),
+            None => {
+                warn!("No data directory found, progress will not be saved");
+                GameStorage::new(MemoryStorage::default())
+            }
+        }
+    }
+
+    //LocalStorage can be disabled by the browser, e.g. in private windows
+    #[cfg(target_family = "wasm")]
+    fn platform() -> Self {
+        let local_storage =
+            web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+        match local_storage {
+            Some(_) => GameStorage::new(local_storage::LocalStorage),
+            None => {
+                warn!("LocalStorage is not available, progress will not be saved");
+                GameStorage::new(MemoryStorage::default())
+            }
+        }
+    }
+}
+
+impl Deref for GameStorage {
+    type Target = dyn SaveStorage;
+
+    fn deref(&self) -> &Self::Target {
+        self.storage.as_ref()
+    }
+}
+
+//Uses the storage of the platform unless a `GameStorage` was inserted
+//before, e.g. a `MemoryStorage` for tests.
+pub struct StoragePlugin;
+
+impl Plugin for StoragePlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<GameStorage>() {
+            app.insert_resource(GameStorage::platform());
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+
+use super::buttons::{change_state_button, Action, ChangeStateButton, DEFAULT_BUTTON_COLOR};
+use crate::state::achievements::{Achievements, AchievementsHandle};
+use crate::state::gamestate::GameState;
+use crate::state::save_file::SaveData;
+use crate::state::timestamp::format_date;
+
+const UNLOCKED_COLOR: Color = Color::GOLD;
+const LOCKED_COLOR: Color = Color::GRAY;
+
+pub struct AchievementsMenuPlugin;
+
+impl Plugin for AchievementsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Achievements), spawn_achievements_menu)
+            .add_systems(
+                Update,
+                (
+                    AchievementsBackButton::button_pressed_system,
+                    AchievementsBackButton::action_pressed_system,
+                )
+                    .run_if(in_state(GameState::Achievements)),
+            )
+            .add_systems(OnExit(GameState::Achievements), despawn_achievements_menu);
+    }
+}
+
+#[derive(Debug, Component)]
+struct AchievementsMenu;
+
+#[derive(Debug, Component, ChangeStateButton)]
+#[action(Back)]
+#[target_state(MainMenu)]
+struct AchievementsBackButton;
+
+fn spawn_achievements_menu(
+    mut commands: Commands,
+    achievements_handle: Res<AchievementsHandle>,
+    achievements: Res<Assets<Achievements>>,
+    save_data: Res<SaveData>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    height: Val::Percent(100.0),
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(50.0)),
+                    row_gap: Val::Px(10.0),
+                    ..default()
+                },
+                ..default()
+            },
+            AchievementsMenu,
+            Name::new("AchievementsMenu"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "ACHIEVEMENTS",
+                    TextStyle {
+                        font_size: 50.0,
+                        ..default()
+                    },
+                ),
+                Name::new("AchievementsTitle"),
+            ));
+
+            let achievements = achievements
+                .get(&achievements_handle.0)
+                .map(|achievements| achievements.achievements.as_slice())
+                .unwrap_or_default();
+            for achievement in achievements {
+                let unlocked_at = save_data.achievements().unlocked_at(&achievement.id);
+                let (color, status) = match unlocked_at {
+                    Some(timestamp) => (UNLOCKED_COLOR, format_date(timestamp)),
+                    None => (LOCKED_COLOR, "Locked".to_string()),
+                };
+                parent
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Px(800.0),
+                                justify_content: JustifyContent::SpaceBetween,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        Name::new("AchievementRow"),
+                    ))
+                    .with_children(|parent| {
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Column,
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    achievement.name.clone(),
+                                    TextStyle {
+                                        font_size: 25.0,
+                                        color,
+                                        ..default()
+                                    },
+                                ));
+                                parent.spawn(TextBundle::from_section(
+                                    achievement.description.clone(),
+                                    TextStyle {
+                                        font_size: 18.0,
+                                        ..default()
+                                    },
+                                ));
+                            });
+                        parent.spawn(TextBundle::from_section(
+                            status,
+                            TextStyle {
+                                font_size: 25.0,
+                                color,
+                                ..default()
+                            },
+                        ));
+                    });
+            }
+
+            parent
+                .spawn(change_state_button(
+                    ButtonBundle {
+                        background_color: DEFAULT_BUTTON_COLOR.into(),
+                        style: Style {
+                            padding: UiRect::all(Val::Px(20.0)),
+                            margin: UiRect::top(Val::Px(20.0)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    AchievementsBackButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "BACK",
+                            TextStyle {
+                                font_size: 35.0,
+                                ..default()
+                            },
+                        ),
+                        Name::new("AchievementsBackButtonText"),
+                    ));
+                });
+        });
+}
+
+fn despawn_achievements_menu(mut commands: Commands, query: Query<Entity, With<AchievementsMenu>>) {
+    for menu in query.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+}
@@ -1,8 +1,17 @@
+pub use crate::input::Action;
+use crate::input::{ActionInput, InputContext};
 use crate::state::gamestate::GameState;
+use crate::state::settings::Settings;
+use crate::ui::prompts::{action_prompt, ActionPrompt};
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 pub use bevy_flappy_pebble_macro::*;
 
 pub const DEFAULT_BUTTON_COLOR: Color = Color::GRAY;
+const BUTTON_BORDER_WIDTH: f32 = 3.0;
+const BUTTON_TWEEN_SECONDS: f32 = 0.12;
+//buttons whose centres are closer than this vertically are in the same row
+const SAME_ROW_DISTANCE: f32 = 10.0;
 
 pub trait ChangeStateButton
 where
@@ -10,37 +19,35 @@ where
 {
     fn name(&self) -> String;
 
-    fn should_change_state_keyboard(input: Res<Input<KeyCode>>) -> bool;
-
-    fn should_change_state_gamepad(input: Res<Input<GamepadButton>>) -> bool;
+    fn actions() -> Vec<Action>;
 
     fn target_state() -> GameState;
 
+    //a click, or Confirm while the button has focus
     fn button_pressed_system(
-        interaction_query: Query<&Interaction, (Changed<Interaction>, With<Self>)>,
-        mut next_state: ResMut<NextState<GameState>>,
-    ) {
-        for interaction in interaction_query.iter() {
-            if *interaction == Interaction::Pressed {
-                next_state.set(Self::target_state());
-            }
-        }
-    }
-
-    fn keyboard_pressed_system(
-        input: Res<Input<KeyCode>>,
+        interaction_query: Query<(Entity, &Interaction), Changed<Interaction>>,
+        query_buttons: Query<Entity, (With<Self>, Without<DisabledButton>)>,
+        selected_button: Res<SelectedButton>,
+        input: ActionInput,
         mut next_state: ResMut<NextState<GameState>>,
     ) {
-        if Self::should_change_state_keyboard(input) {
+        let clicked = interaction_query.iter().any(|(entity, interaction)| {
+            *interaction == Interaction::Pressed && query_buttons.contains(entity)
+        });
+        let confirmed = input.just_pressed(Action::Confirm)
+            && selected_button
+                .entity
+                .is_some_and(|entity| query_buttons.contains(entity));
+        if clicked || confirmed {
             next_state.set(Self::target_state());
         }
     }
 
-    fn gamepad_button_pressed_system(
-        input: Res<Input<GamepadButton>>,
-        mut next_state: ResMut<NextState<GameState>>,
-    ) {
-        if Self::should_change_state_gamepad(input) {
+    fn action_pressed_system(input: ActionInput, mut next_state: ResMut<NextState<GameState>>) {
+        if Self::actions()
+            .into_iter()
+            .any(|action| input.just_pressed(action))
+        {
             next_state.set(Self::target_state());
         }
     }
@@ -51,9 +58,440 @@ where
     T: ChangeStateButton + Bundle,
 {
     let name = component.name();
+    (
+        focusable_button(button_bundle, name),
+        component,
+        ButtonActions(T::actions()),
+    )
+}
+
+//A button that can be focused and pressed with Confirm. Pressing it is up
+//to the caller, see `SelectedButton::entity`.
+pub fn focusable_button(mut button_bundle: ButtonBundle, name: String) -> impl Bundle {
+    button_bundle.style.border = UiRect::all(Val::Px(BUTTON_BORDER_WIDTH));
+    button_bundle.style.align_items = AlignItems::Center;
+    button_bundle.style.column_gap = Val::Px(10.0);
+    let look = ButtonState::Normal.look();
+    button_bundle.background_color = look.color.into();
+    button_bundle.border_color = look.border.into();
 
-    (button_bundle, component, Name::new(name))
+    (
+        button_bundle,
+        FocusableButton,
+        ButtonAnimation::default(),
+        Name::new(name),
+    )
+}
+
+#[derive(Debug, Component)]
+pub struct FocusableButton;
+
+//actions that press the button, shown as a prompt next to its label
+#[derive(Debug, Component)]
+struct ButtonActions(Vec<Action>);
+
+//Disabled buttons ignore clicks and focus but stay visible.
+#[derive(Debug, Component)]
+pub struct DisabledButton;
+
+//A button whose press is still in progress, e.g. waiting for a new binding.
+#[derive(Debug, Component)]
+pub struct ActiveButton;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum ButtonState {
+    #[default]
+    Normal,
+    Hovered,
+    Pressed,
+    Focused,
+    Active,
+    Disabled,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ButtonLook {
+    color: Color,
+    border: Color,
+    scale: f32,
+}
+
+impl ButtonState {
+    fn look(self) -> ButtonLook {
+        match self {
+            ButtonState::Normal => ButtonLook {
+                color: DEFAULT_BUTTON_COLOR,
+                border: Color::NONE,
+                scale: 1.0,
+            },
+            ButtonState::Hovered => ButtonLook {
+                color: Color::rgb(0.6, 0.6, 0.6),
+                border: Color::SILVER,
+                scale: 1.05,
+            },
+            ButtonState::Pressed => ButtonLook {
+                color: Color::DARK_GRAY,
+                border: Color::WHITE,
+                scale: 0.95,
+            },
+            ButtonState::Focused => ButtonLook {
+                color: Color::rgb(0.55, 0.55, 0.55),
+                border: Color::WHITE,
+                scale: 1.05,
+            },
+            ButtonState::Active => ButtonLook {
+                color: Color::ORANGE,
+                border: Color::WHITE,
+                scale: 1.0,
+            },
+            ButtonState::Disabled => ButtonLook {
+                color: Color::rgba(0.3, 0.3, 0.3, 0.6),
+                border: Color::NONE,
+                scale: 1.0,
+            },
+        }
+    }
+}
+
+impl ButtonLook {
+    fn lerp(self, other: ButtonLook, t: f32) -> ButtonLook {
+        let lerp_color = |from: Color, to: Color| {
+            let from = from.as_rgba_f32();
+            let to = to.as_rgba_f32();
+            Color::rgba(
+                from[0] + (to[0] - from[0]) * t,
+                from[1] + (to[1] - from[1]) * t,
+                from[2] + (to[2] - from[2]) * t,
+                from[3] + (to[3] - from[3]) * t,
+            )
+        };
+        ButtonLook {
+            color: lerp_color(self.color, other.color),
+            border: lerp_color(self.border, other.border),
+            scale: self.scale + (other.scale - self.scale) * t,
+        }
+    }
+}
+
+//tweens from the look the button had when its state last changed
+#[derive(Debug, Component)]
+struct ButtonAnimation {
+    state: ButtonState,
+    from: ButtonLook,
+    progress: f32,
+}
+
+impl Default for ButtonAnimation {
+    fn default() -> Self {
+        ButtonAnimation {
+            state: ButtonState::Normal,
+            from: ButtonState::Normal.look(),
+            progress: 1.0,
+        }
+    }
+}
+
+impl ButtonAnimation {
+    fn current(&self) -> ButtonLook {
+        self.from.lerp(self.state.look(), self.progress)
+    }
+}
+
+//The button that Confirm activates. The focused position is remembered per
+//screen, so coming back to a menu focuses the same button again. The screen
+//a run is started from forgets it, so after playing Confirm starts, restarts
+//or unpauses like the first button of each screen always did.
+#[derive(Debug, Resource, Default)]
+pub struct SelectedButton {
+    entity: Option<Entity>,
+    screen: Option<GameState>,
+    remembered: HashMap<GameState, usize>,
+}
+
+impl SelectedButton {
+    pub fn entity(&self) -> Option<Entity> {
+        self.entity
+    }
+}
+
+pub struct ButtonsPlugin;
+
+impl Plugin for ButtonsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectedButton>().add_systems(
+            Update,
+            (
+                restore_focus,
+                (focus_hovered_button, navigate_focus),
+                update_button_states,
+                animate_buttons,
+                spawn_button_prompts,
+                update_button_prompts,
+            )
+                .chain(),
+        );
+    }
 }
 
-#[derive(Debug, Resource)]
-pub struct SelectedButton;
+type FocusableButtons<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Node,
+        &'static GlobalTransform,
+        Has<DisabledButton>,
+    ),
+    With<FocusableButton>,
+>;
+
+//buttons and their centres in reading order, left to right and top to bottom
+fn focus_positions(query_buttons: &FocusableButtons) -> Vec<(Entity, Vec2)> {
+    let mut buttons: Vec<_> = query_buttons
+        .iter()
+        //not laid out yet, the order would be meaningless
+        .filter(|(_, node, _, disabled)| node.size() != Vec2::ZERO && !disabled)
+        .map(|(entity, _, transform, _)| (entity, transform.translation().truncate()))
+        .collect();
+    buttons.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+    buttons
+}
+
+fn focus_order(query_buttons: &FocusableButtons) -> Vec<Entity> {
+    focus_positions(query_buttons)
+        .into_iter()
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+//The closest button in the direction: left and right stay in the row, up and
+//down go to the nearest row and the closest button in it.
+fn neighbour(buttons: &[(Entity, Vec2)], current: usize, direction: Vec2) -> Option<usize> {
+    let from = buttons[current].1;
+    let candidates: Vec<_> = buttons
+        .iter()
+        .enumerate()
+        .map(|(index, (_, position))| (index, *position - from))
+        .filter(|(_, offset)| {
+            let along = offset.dot(direction);
+            let across = offset.perp_dot(direction).abs();
+            if direction.x != 0.0 {
+                along > 0.0 && across < SAME_ROW_DISTANCE
+            } else {
+                along > SAME_ROW_DISTANCE
+            }
+        })
+        .collect();
+    let nearest = candidates
+        .iter()
+        .map(|(_, offset)| offset.dot(direction))
+        .min_by(f32::total_cmp)?;
+    candidates
+        .into_iter()
+        .filter(|(_, offset)| offset.dot(direction) < nearest + SAME_ROW_DISTANCE)
+        .min_by(|(_, a), (_, b)| {
+            let across = |offset: &Vec2| offset.perp_dot(direction).abs();
+            across(a).total_cmp(&across(b))
+        })
+        .map(|(index, _)| index)
+}
+
+fn set_focus(
+    selected_button: &mut SelectedButton,
+    state: GameState,
+    buttons: &[Entity],
+    index: usize,
+) {
+    selected_button.entity = buttons.get(index).copied();
+    selected_button.screen = Some(state);
+    selected_button.remembered.insert(state, index);
+}
+
+fn restore_focus(
+    game_state: Res<State<GameState>>,
+    mut selected_button: ResMut<SelectedButton>,
+    query_buttons: FocusableButtons,
+) {
+    let state = *game_state.get();
+    if !InputContext::Menu.is_active(state) {
+        if let Some(screen) = selected_button.screen {
+            selected_button.entity = None;
+            selected_button.screen = None;
+            selected_button.remembered.remove(&screen);
+        }
+        return;
+    }
+    if selected_button
+        .entity
+        .is_some_and(|entity| query_buttons.contains(entity))
+    {
+        return;
+    }
+
+    let buttons = focus_order(&query_buttons);
+    if buttons.is_empty() {
+        if selected_button.entity.is_some() {
+            selected_button.entity = None;
+        }
+        return;
+    }
+    let index = selected_button
+        .remembered
+        .get(&state)
+        .copied()
+        .unwrap_or_default()
+        .min(buttons.len() - 1);
+    set_focus(&mut selected_button, state, &buttons, index);
+}
+
+fn focus_hovered_button(
+    game_state: Res<State<GameState>>,
+    mut selected_button: ResMut<SelectedButton>,
+    query_buttons: FocusableButtons,
+    query_interactions: Query<(Entity, &Interaction), Changed<Interaction>>,
+) {
+    let Some(hovered) = query_interactions
+        .iter()
+        .find(|(_, interaction)| **interaction == Interaction::Hovered)
+        .map(|(entity, _)| entity)
+    else {
+        return;
+    };
+    let buttons = focus_order(&query_buttons);
+    if let Some(index) = buttons.iter().position(|entity| *entity == hovered) {
+        set_focus(&mut selected_button, *game_state.get(), &buttons, index);
+    }
+}
+
+fn navigate_focus(
+    input: ActionInput,
+    game_state: Res<State<GameState>>,
+    mut selected_button: ResMut<SelectedButton>,
+    query_buttons: FocusableButtons,
+) {
+    //UI coordinates grow downwards
+    let Some((direction, step)) = input.read().find_map(|event| match event.action {
+        Action::NavigateUp => Some((Vec2::NEG_Y, -1)),
+        Action::NavigateLeft => Some((Vec2::NEG_X, -1)),
+        Action::NavigateDown => Some((Vec2::Y, 1)),
+        Action::NavigateRight => Some((Vec2::X, 1)),
+        _ => None,
+    }) else {
+        return;
+    };
+
+    let buttons = focus_positions(&query_buttons);
+    if buttons.is_empty() {
+        return;
+    }
+    let current = selected_button
+        .entity
+        .and_then(|selected| buttons.iter().position(|(entity, _)| *entity == selected))
+        .unwrap_or_default();
+    //nothing in that direction, move through the buttons in reading order
+    let index = neighbour(&buttons, current, direction)
+        .unwrap_or_else(|| (current as isize + step).rem_euclid(buttons.len() as isize) as usize);
+    let buttons: Vec<_> = buttons.into_iter().map(|(entity, _)| entity).collect();
+    set_focus(&mut selected_button, *game_state.get(), &buttons, index);
+}
+
+type AnimatedButtons<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Interaction,
+        Has<DisabledButton>,
+        Has<ActiveButton>,
+        &'static mut ButtonAnimation,
+    ),
+>;
+
+fn update_button_states(selected_button: Res<SelectedButton>, mut query_buttons: AnimatedButtons) {
+    for (entity, interaction, disabled, active, mut animation) in query_buttons.iter_mut() {
+        let state = if disabled {
+            ButtonState::Disabled
+        } else if active {
+            ButtonState::Active
+        } else {
+            match interaction {
+                Interaction::Pressed => ButtonState::Pressed,
+                Interaction::Hovered => ButtonState::Hovered,
+                Interaction::None if selected_button.entity == Some(entity) => ButtonState::Focused,
+                Interaction::None => ButtonState::Normal,
+            }
+        };
+        if animation.state != state {
+            animation.from = animation.current();
+            animation.state = state;
+            animation.progress = 0.0;
+        }
+    }
+}
+
+fn animate_buttons(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut query_buttons: Query<(
+        &mut ButtonAnimation,
+        &mut BackgroundColor,
+        &mut BorderColor,
+        &mut Transform,
+    )>,
+) {
+    for (mut animation, mut background, mut border, mut transform) in query_buttons.iter_mut() {
+        if animation.progress >= 1.0 {
+            continue;
+        }
+        animation.progress = if settings.reduced_motion {
+            1.0
+        } else {
+            f32::min(
+                animation.progress + time.delta_seconds() / BUTTON_TWEEN_SECONDS,
+                1.0,
+            )
+        };
+
+        let look = animation.current();
+        *background = look.color.into();
+        *border = look.border.into();
+        //reduced motion keeps the colours but not the size changes
+        let scale = if settings.reduced_motion {
+            1.0
+        } else {
+            look.scale
+        };
+        transform.scale = Vec3::new(scale, scale, 1.0);
+    }
+}
+
+fn spawn_button_prompts(
+    mut commands: Commands,
+    query_buttons: Query<(Entity, &ButtonActions), Added<ButtonActions>>,
+) {
+    for (entity, actions) in query_buttons.iter() {
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn(action_prompt(actions.0.clone()));
+        });
+    }
+}
+
+//the focused button can also be pressed with Confirm
+fn update_button_prompts(
+    selected_button: Res<SelectedButton>,
+    query_buttons: Query<(Entity, &ButtonActions, &Children)>,
+    mut query_prompts: Query<&mut ActionPrompt>,
+) {
+    for (entity, actions, children) in query_buttons.iter() {
+        let mut prompt_actions = actions.0.clone();
+        if selected_button.entity == Some(entity) {
+            prompt_actions.insert(0, Action::Confirm);
+        }
+        let mut prompts = query_prompts.iter_many_mut(children);
+        while let Some(mut prompt) = prompts.fetch_next() {
+            if prompt.0 != prompt_actions {
+                prompt.0 = prompt_actions.clone();
+            }
+        }
+    }
+}
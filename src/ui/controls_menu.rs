@@ -0,0 +1,433 @@
+use bevy::prelude::*;
+
+use crate::input::{action_just_pressed, Action, ActionInput, ActionMap, Binding, BindingCapture};
+use crate::state::gamestate::GameState;
+use crate::state::settings::Settings;
+use crate::ui::buttons::{focusable_button, ActiveButton, SelectedButton, DEFAULT_BUTTON_COLOR};
+
+pub struct ControlsMenuPlugin;
+
+impl Plugin for ControlsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Controls), spawn_controls_menu)
+            .add_systems(
+                Update,
+                (
+                    //capture before handling presses, so the click on ADD
+                    //is not captured as the new binding
+                    (capture_binding, control_button_pressed).chain(),
+                    update_binding_labels.run_if(resource_changed::<Settings>()),
+                    update_capture_prompt.run_if(resource_changed::<BindingCapture>()),
+                    go_back.run_if(action_just_pressed(Action::Back)),
+                )
+                    .run_if(in_state(GameState::Controls)),
+            )
+            .add_systems(
+                OnExit(GameState::Controls),
+                (despawn_controls_menu, cancel_capture),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ControlButtonKind {
+    Add(Action),
+    Clear(Action),
+    ResetDefaults,
+    Back,
+}
+
+#[derive(Debug, Component)]
+struct ControlsMenu;
+
+#[derive(Debug, Component)]
+struct ControlButton(ControlButtonKind);
+
+#[derive(Debug, Component)]
+struct BindingsLabel(Action);
+
+#[derive(Debug, Component)]
+struct ControlsPrompt;
+
+fn bindings_text(action_map: &ActionMap, action: Action) -> String {
+    let bindings = action_map.bindings(action);
+    if bindings.is_empty() {
+        return "-".to_string();
+    }
+    bindings
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn spawn_control_button(
+    parent: &mut ChildBuilder,
+    kind: ControlButtonKind,
+    text: &str,
+    font_size: f32,
+) {
+    parent
+        .spawn((
+            focusable_button(
+                ButtonBundle {
+                    background_color: DEFAULT_BUTTON_COLOR.into(),
+                    style: Style {
+                        padding: UiRect::axes(Val::Px(15.0), Val::Px(5.0)),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                format!("{kind:?}ControlButton"),
+            ),
+            ControlButton(kind),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font_size,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn spawn_controls_menu(mut commands: Commands, settings: Res<Settings>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    height: Val::Percent(100.0),
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(50.0)),
+                    row_gap: Val::Px(15.0),
+                    ..default()
+                },
+                ..default()
+            },
+            ControlsMenu,
+            Name::new("ControlsMenu"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "CONTROLS",
+                    TextStyle {
+                        font_size: 50.0,
+                        ..default()
+                    },
+                ),
+                Name::new("ControlsMenuTitle"),
+            ));
+
+            for action in Action::all() {
+                parent
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Px(700.0),
+                                align_items: AlignItems::Center,
+                                column_gap: Val::Px(10.0),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        Name::new(format!("{action:?}ControlRow")),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(
+                            TextBundle::from_section(
+                                action.label(),
+                                TextStyle {
+                                    font_size: 25.0,
+                                    ..default()
+                                },
+                            )
+                            .with_style(Style {
+                                width: Val::Px(130.0),
+                                ..default()
+                            }),
+                        );
+                        parent.spawn((
+                            TextBundle::from_section(
+                                bindings_text(&settings.controls, action),
+                                TextStyle {
+                                    font_size: 20.0,
+                                    ..default()
+                                },
+                            )
+                            .with_style(Style {
+                                flex_grow: 1.0,
+                                flex_basis: Val::Px(0.0),
+                                ..default()
+                            }),
+                            BindingsLabel(action),
+                        ));
+                        spawn_control_button(parent, ControlButtonKind::Add(action), "ADD", 20.0);
+                        spawn_control_button(
+                            parent,
+                            ControlButtonKind::Clear(action),
+                            "CLEAR",
+                            20.0,
+                        );
+                    });
+            }
+
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 20.0,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                ),
+                ControlsPrompt,
+                Name::new("ControlsPrompt"),
+            ));
+
+            spawn_control_button(
+                parent,
+                ControlButtonKind::ResetDefaults,
+                "RESET DEFAULTS",
+                25.0,
+            );
+            spawn_control_button(parent, ControlButtonKind::Back, "BACK", 35.0);
+        });
+}
+
+//a click, or Confirm while the button has focus
+#[allow(clippy::too_many_arguments)]
+fn control_button_pressed(
+    interaction_query: Query<(&Interaction, &ControlButton), Changed<Interaction>>,
+    query_buttons: Query<&ControlButton>,
+    selected_button: Res<SelectedButton>,
+    input: ActionInput,
+    mut settings: ResMut<Settings>,
+    mut capture: ResMut<BindingCapture>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    //a click that was just captured as a binding does not press the button under it
+    if capture.is_changed() {
+        return;
+    }
+    let clicked = interaction_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, button)| button);
+    let confirmed = selected_button
+        .entity()
+        .filter(|_| input.just_pressed(Action::Confirm))
+        .and_then(|entity| query_buttons.get(entity).ok());
+    for button in clicked.chain(confirmed) {
+        match button.0 {
+            ControlButtonKind::Add(action) => capture.0 = Some(action),
+            ControlButtonKind::Clear(action) => settings.controls.clear_bindings(action),
+            ControlButtonKind::ResetDefaults => settings.controls = ActionMap::default(),
+            ControlButtonKind::Back => next_state.set(GameState::Settings),
+        }
+    }
+}
+
+//The first key, mouse button, gamepad button or touch pressed becomes the new
+//binding, except for the bindings of Back, which cancel. This runs before the
+//buttons are pressed, so the press that starts a capture is never captured.
+#[allow(clippy::too_many_arguments)]
+fn capture_binding(
+    mut capture: ResMut<BindingCapture>,
+    mut settings: ResMut<Settings>,
+    keyboard: Res<Input<KeyCode>>,
+    mouse: Res<Input<MouseButton>>,
+    gamepad: Res<Input<GamepadButton>>,
+    touches: Res<Touches>,
+    mut query_prompt: Query<&mut Text, With<ControlsPrompt>>,
+) {
+    let Some(action) = capture.0 else {
+        return;
+    };
+
+    let binding = keyboard
+        .get_just_pressed()
+        .next()
+        .map(|key| Binding::Key(*key))
+        .or_else(|| {
+            mouse
+                .get_just_pressed()
+                .next()
+                .map(|button| Binding::Mouse(*button))
+        })
+        .or_else(|| {
+            gamepad
+                .get_just_pressed()
+                .next()
+                .map(|button| Binding::Gamepad(button.button_type))
+        })
+        .or_else(|| touches.any_just_pressed().then_some(Binding::Touch));
+    let Some(binding) = binding else {
+        return;
+    };
+
+    capture.0 = None;
+    let cancelled = settings.controls.bindings(Action::Back).contains(&binding);
+    let conflicts = if cancelled {
+        Vec::new()
+    } else {
+        let conflicts = settings.controls.conflicts(action, binding);
+        settings.controls.add_binding(action, binding);
+        conflicts
+    };
+
+    let warning = if conflicts.is_empty() {
+        String::new()
+    } else {
+        let conflicts: Vec<_> = conflicts.iter().map(|action| action.label()).collect();
+        format!(
+            "Warning: {binding} is also bound to {}",
+            conflicts.join(", ")
+        )
+    };
+    for mut text in query_prompt.iter_mut() {
+        let section = text.sections.first_mut().expect("to have a TextSection");
+        section.value = warning.clone();
+    }
+}
+
+//the ADD button of the action being captured stays highlighted
+fn update_capture_prompt(
+    mut commands: Commands,
+    capture: Res<BindingCapture>,
+    mut query_prompt: Query<&mut Text, With<ControlsPrompt>>,
+    query_buttons: Query<(Entity, &ControlButton, Has<ActiveButton>)>,
+) {
+    for (entity, button, active) in query_buttons.iter() {
+        let capturing =
+            matches!(button.0, ControlButtonKind::Add(action) if capture.0 == Some(action));
+        if capturing && !active {
+            commands.entity(entity).insert(ActiveButton);
+        } else if !capturing && active {
+            commands.entity(entity).remove::<ActiveButton>();
+        }
+    }
+
+    let Some(action) = capture.0 else {
+        return;
+    };
+    for mut text in query_prompt.iter_mut() {
+        let section = text.sections.first_mut().expect("to have a TextSection");
+        section.value = format!(
+            "Press a key or button for {}, or Back to cancel...",
+            action.label()
+        );
+    }
+}
+
+fn update_binding_labels(
+    settings: Res<Settings>,
+    mut query_labels: Query<(&mut Text, &BindingsLabel)>,
+) {
+    for (mut text, label) in query_labels.iter_mut() {
+        let section = text.sections.first_mut().expect("to have a TextSection");
+        section.value = bindings_text(&settings.controls, label.0);
+    }
+}
+
+fn go_back(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::Settings);
+}
+
+fn cancel_capture(mut capture: ResMut<BindingCapture>) {
+    capture.0 = None;
+}
+
+fn despawn_controls_menu(mut commands: Commands, query: Query<Entity, With<ControlsMenu>>) {
+    for menu in query.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::core::FrameCount;
+
+    use super::*;
+    use crate::input::ActionEvent;
+
+    //the capture and press systems with the ADD button of Flap under the mouse
+    fn capturing_app() -> App {
+        let mut app = App::new();
+        app.add_state::<GameState>()
+            .add_event::<ActionEvent>()
+            .init_resource::<BindingCapture>()
+            .init_resource::<Settings>()
+            .init_resource::<SelectedButton>()
+            .init_resource::<Input<KeyCode>>()
+            .init_resource::<Input<MouseButton>>()
+            .init_resource::<Input<GamepadButton>>()
+            .init_resource::<Touches>()
+            .init_resource::<FrameCount>()
+            .add_systems(Update, (capture_binding, control_button_pressed).chain());
+        //freshly inserted resources count as changed once
+        app.update();
+        app.world.spawn((
+            Interaction::Pressed,
+            ControlButton(ControlButtonKind::Add(Action::Flap)),
+        ));
+        app.world
+            .resource_mut::<Input<MouseButton>>()
+            .press(MouseButton::Left);
+        app.update();
+        app.world.resource_mut::<Input<MouseButton>>().clear();
+        app
+    }
+
+    fn press_key(app: &mut App, key: KeyCode) {
+        app.world.resource_mut::<Input<KeyCode>>().press(key);
+        app.update();
+        app.world.resource_mut::<Input<KeyCode>>().clear();
+    }
+
+    fn flap_bindings(app: &App) -> Vec<Binding> {
+        app.world
+            .resource::<Settings>()
+            .controls
+            .bindings(Action::Flap)
+            .to_vec()
+    }
+
+    #[test]
+    fn click_starting_the_capture_is_not_bound() {
+        let mut app = capturing_app();
+        app.update();
+
+        assert_eq!(app.world.resource::<BindingCapture>().0, Some(Action::Flap));
+        assert_eq!(
+            flap_bindings(&app),
+            ActionMap::default().bindings(Action::Flap)
+        );
+    }
+
+    #[test]
+    fn back_cancels_the_capture() {
+        let mut app = capturing_app();
+        press_key(&mut app, KeyCode::Escape);
+
+        assert_eq!(app.world.resource::<BindingCapture>().0, None);
+        assert_eq!(
+            flap_bindings(&app),
+            ActionMap::default().bindings(Action::Flap)
+        );
+    }
+
+    #[test]
+    fn first_press_is_bound() {
+        let mut app = capturing_app();
+        press_key(&mut app, KeyCode::K);
+
+        assert_eq!(app.world.resource::<BindingCapture>().0, None);
+        assert!(flap_bindings(&app).contains(&Binding::Key(KeyCode::K)));
+    }
+}
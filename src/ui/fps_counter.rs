@@ -0,0 +1,77 @@
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+
+use crate::state::settings::Settings;
+
+pub struct FpsCounterPlugin;
+
+impl Plugin for FpsCounterPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+        app.add_systems(Startup, spawn_fps_counter).add_systems(
+            Update,
+            (
+                toggle_fps_counter.run_if(resource_changed::<Settings>()),
+                update_fps_counter,
+            ),
+        );
+    }
+}
+
+#[derive(Debug, Component)]
+struct FpsCounter;
+
+fn spawn_fps_counter(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 20.0,
+                color: Color::YELLOW,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            right: Val::Px(10.0),
+            ..default()
+        }),
+        FpsCounter,
+        Name::new("FpsCounter"),
+    ));
+}
+
+fn toggle_fps_counter(
+    settings: Res<Settings>,
+    mut query_counter: Query<&mut Visibility, With<FpsCounter>>,
+) {
+    for mut visibility in query_counter.iter_mut() {
+        *visibility = if settings.show_fps {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+fn update_fps_counter(
+    diagnostics: Res<DiagnosticsStore>,
+    mut query_counter: Query<(&mut Text, &Visibility), With<FpsCounter>>,
+) {
+    let fps = diagnostics
+        .get(FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed());
+    for (mut text, visibility) in query_counter.iter_mut() {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+        let section = text.sections.first_mut().expect("to have a TextSection");
+        section.value = match fps {
+            Some(fps) => format!("FPS: {fps:.0}"),
+            None => "FPS: -".to_string(),
+        };
+    }
+}
@@ -1,7 +1,6 @@
-use crate::gamepad_util::gamepad_just_pressed;
-use bevy::input::common_conditions::input_just_pressed;
+use crate::input::{action_just_pressed, Action};
+use crate::state::settings::{DisplayMode, Settings};
 use bevy::prelude::*;
-use bevy::window::WindowMode;
 
 pub struct FullScreenPlugin;
 
@@ -10,22 +9,16 @@ impl Plugin for FullScreenPlugin {
         if cfg!(not(target_family = "wasm")) {
             app.add_systems(
                 Update,
-                toggle_fullscreen.run_if(
-                    input_just_pressed(KeyCode::F)
-                        .or_else(gamepad_just_pressed(GamepadButtonType::North)),
-                ),
+                toggle_fullscreen.run_if(action_just_pressed(Action::ToggleFullscreen)),
             );
         }
     }
 }
 
-fn toggle_fullscreen(mut window: Query<&mut Window>) {
-    let mut window = window.single_mut();
-
-    let current_mode = window.mode;
-
-    window.mode = match current_mode {
-        WindowMode::Windowed => WindowMode::Fullscreen,
-        _ => WindowMode::Windowed,
+//the window itself is updated when the settings are applied
+fn toggle_fullscreen(mut settings: ResMut<Settings>) {
+    settings.display_mode = match settings.display_mode {
+        DisplayMode::Windowed => DisplayMode::Fullscreen,
+        _ => DisplayMode::Windowed,
     }
 }
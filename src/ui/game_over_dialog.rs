@@ -1,45 +1,168 @@
-use super::buttons::{change_state_button, ChangeStateButton, DEFAULT_BUTTON_COLOR};
+use super::buttons::{change_state_button, Action, ChangeStateButton, DEFAULT_BUTTON_COLOR};
 use bevy::prelude::*;
 
+use crate::biome::ActiveBiome;
+use crate::state::gamescore::Score;
+use crate::state::leaderboard::{record_run, LastRunRank};
+use crate::state::medals::{MedalThresholds, MedalThresholdsHandle};
+use crate::state::run_info::RunInfo;
+use crate::state::scoring_rule::ScoringRule;
+use crate::state::settings::{GameMode, Settings};
+use crate::state::statistics::RunStatistics;
 use crate::state::{gamescore::GameScore, gamestate::GameState};
 
+const COUNT_UP_SECONDS: f32 = 0.8;
+//between the start of one result row and the next
+const COUNT_UP_STAGGER_SECONDS: f32 = 0.2;
+
 pub struct GameOverDialogPlugin;
 
 impl Plugin for GameOverDialogPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::GameOver), spawn_game_over_dialog)
-            .add_systems(
-                Update,
-                (
-                    RestartButton::button_pressed_system,
-                    RestartButton::keyboard_pressed_system,
-                    RestartButton::gamepad_button_pressed_system,
-                    MainMenuButton::button_pressed_system,
-                    MainMenuButton::keyboard_pressed_system,
-                    MainMenuButton::gamepad_button_pressed_system,
-                )
-                    .run_if(in_state(GameState::GameOver)),
+        app.add_systems(
+            OnEnter(GameState::GameOver),
+            spawn_game_over_dialog.after(record_run),
+        )
+        .add_systems(
+            Update,
+            (
+                RestartButton::button_pressed_system,
+                MainMenuButton::button_pressed_system,
+                MainMenuButton::action_pressed_system,
+                count_up_results,
             )
-            .add_systems(OnExit(GameState::GameOver), despawn_game_over_dialog);
+                .run_if(in_state(GameState::GameOver)),
+        )
+        .add_systems(OnExit(GameState::GameOver), despawn_game_over_dialog);
     }
 }
 
 #[derive(Component, ChangeStateButton)]
-#[keyboard(Return, Space)]
 #[target_state(Playing)]
-#[gamepad(South)]
 struct RestartButton;
 
 #[derive(Component, ChangeStateButton)]
-#[keyboard(Escape)]
+#[action(Back)]
 #[target_state(MainMenu)]
-#[gamepad(East)]
 struct MainMenuButton;
 
 #[derive(Component)]
 struct GameOverDialog;
 
-fn spawn_game_over_dialog(mut commands: Commands, game_score: Res<GameScore>) {
+#[derive(Debug, Clone, Copy)]
+enum ResultValue {
+    Count(u32),
+    //formatted by the scoring rule of the run
+    Score(Score),
+    Seconds(f32),
+    //a fraction, shown as a percentage
+    Percent(Option<f32>),
+}
+
+impl ResultValue {
+    //the value `progress` of the way from zero
+    fn format(self, progress: f32, rule: &dyn ScoringRule) -> String {
+        match self {
+            ResultValue::Count(count) => ((count as f32 * progress) as u32).to_string(),
+            ResultValue::Score(score) => rule.format_score((score as f32 * progress) as Score),
+            ResultValue::Seconds(seconds) => format!("{:.1}s", seconds * progress),
+            ResultValue::Percent(Some(fraction)) => {
+                format!("{:.0}%", fraction * progress * 100.0)
+            }
+            ResultValue::Percent(None) => "-".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Component)]
+struct CountUp {
+    value: ResultValue,
+    elapsed: f32,
+    delay: f32,
+}
+
+fn spawn_result_row(
+    parent: &mut ChildBuilder,
+    label: &str,
+    value: ResultValue,
+    index: usize,
+    rule: &dyn ScoringRule,
+) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Px(400.0),
+                    justify_content: JustifyContent::SpaceBetween,
+                    ..default()
+                },
+                ..default()
+            },
+            Name::new(format!("{label}ResultRow")),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font_size: 30.0,
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                TextBundle::from_section(
+                    value.format(0.0, rule),
+                    TextStyle {
+                        font_size: 30.0,
+                        ..default()
+                    },
+                ),
+                CountUp {
+                    value,
+                    elapsed: 0.0,
+                    delay: index as f32 * COUNT_UP_STAGGER_SECONDS,
+                },
+            ));
+        });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_game_over_dialog(
+    mut commands: Commands,
+    game_score: Res<GameScore>,
+    run_info: Res<RunInfo>,
+    run_statistics: Res<RunStatistics>,
+    medal_thresholds_handle: Res<MedalThresholdsHandle>,
+    medal_thresholds: Res<Assets<MedalThresholds>>,
+    active_biome: Res<ActiveBiome>,
+    last_run_rank: Res<LastRunRank>,
+) {
+    let score = game_score.get_current_score();
+    //the thresholds count moai, so only ranked classic runs earn medals
+    let medal = medal_thresholds
+        .get(&medal_thresholds_handle.0)
+        .filter(|_| run_info.game_mode() == GameMode::Classic && run_info.is_ranked())
+        .and_then(|thresholds| thresholds.medal(score));
+    let results = [
+        (game_score.label(), ResultValue::Score(score)),
+        (
+            "Survival time",
+            ResultValue::Seconds(run_statistics.seconds()),
+        ),
+        ("Flaps", ResultValue::Count(run_statistics.flaps())),
+        (
+            "Best streak",
+            ResultValue::Count(run_statistics.best_streak()),
+        ),
+        (
+            "Near misses",
+            ResultValue::Count(run_statistics.near_misses()),
+        ),
+        (
+            "Gap accuracy",
+            ResultValue::Percent(run_statistics.average_accuracy()),
+        ),
+    ];
+
     commands
         .spawn((
             NodeBundle {
@@ -67,15 +190,31 @@ fn spawn_game_over_dialog(mut commands: Commands, game_score: Res<GameScore>) {
                 ),
                 Name::new("GameOverDialogText"),
             ));
+            if let Some(medal) = medal {
+                parent.spawn((
+                    TextBundle::from_section(
+                        format!("{} MEDAL", medal.label()),
+                        TextStyle {
+                            font_size: 40.0,
+                            color: medal.color(),
+                            ..default()
+                        },
+                    ),
+                    Name::new("MedalLabel"),
+                ));
+            }
+            for (index, (label, value)) in results.into_iter().enumerate() {
+                spawn_result_row(parent, label, value, index, game_score.rule());
+            }
             parent.spawn((
                 TextBundle::from_section(
-                    "Score: ".to_string() + &game_score.get_current_score().to_string(),
+                    "Died in: ".to_string() + &active_biome.get().name,
                     TextStyle {
                         font_size: 30.0,
                         ..default()
                     },
                 ),
-                Name::new("GameScoreLabel"),
+                Name::new("BiomeLabel"),
             ));
             if game_score.is_new_high_score() {
                 parent.spawn((
@@ -91,7 +230,8 @@ fn spawn_game_over_dialog(mut commands: Commands, game_score: Res<GameScore>) {
             } else {
                 parent.spawn((
                     TextBundle::from_section(
-                        "Highscore: ".to_string() + &game_score.get_high_score().to_string(),
+                        "Highscore: ".to_string()
+                            + &game_score.format_score(game_score.get_high_score()),
                         TextStyle {
                             font_size: 30.0,
                             ..default()
@@ -100,6 +240,19 @@ fn spawn_game_over_dialog(mut commands: Commands, game_score: Res<GameScore>) {
                     Name::new("HighScoreLabel"),
                 ));
             }
+            if let Some(rank) = last_run_rank.0 {
+                parent.spawn((
+                    TextBundle::from_section(
+                        format!("#{} on the leaderboard!", rank + 1),
+                        TextStyle {
+                            font_size: 30.0,
+                            color: Color::GOLD,
+                            ..default()
+                        },
+                    ),
+                    Name::new("LeaderboardRankLabel"),
+                ));
+            }
 
             parent
                 .spawn(change_state_button(
@@ -153,6 +306,24 @@ fn spawn_game_over_dialog(mut commands: Commands, game_score: Res<GameScore>) {
         });
 }
 
+fn count_up_results(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    game_score: Res<GameScore>,
+    mut query_count_up: Query<(&mut Text, &mut CountUp)>,
+) {
+    for (mut text, mut count_up) in query_count_up.iter_mut() {
+        count_up.elapsed += time.delta_seconds();
+        let progress = if settings.reduced_motion {
+            1.0
+        } else {
+            ((count_up.elapsed - count_up.delay) / COUNT_UP_SECONDS).clamp(0.0, 1.0)
+        };
+        let section = text.sections.first_mut().expect("to have a TextSection");
+        section.value = count_up.value.format(progress, game_score.rule());
+    }
+}
+
 fn despawn_game_over_dialog(
     mut commands: Commands,
     dialog_query: Query<Entity, With<GameOverDialog>>,
@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+
+use super::buttons::{change_state_button, Action, ChangeStateButton, DEFAULT_BUTTON_COLOR};
+use crate::state::gamestate::GameState;
+use crate::state::leaderboard::{LastRunRank, LeaderboardEntry};
+use crate::state::run_info::RunInfo;
+use crate::state::save_file::SaveData;
+use crate::state::scoring_rule::ScoringRule;
+use crate::state::settings::Settings;
+
+const HIGHLIGHT_COLOR: Color = Color::GOLD;
+const HEADER_COLOR: Color = Color::GRAY;
+//column titles and widths
+const COLUMNS: [(&str, f32); 6] = [
+    ("#", 50.0),
+    ("NAME", 220.0),
+    ("SCORE", 100.0),
+    ("DATE", 160.0),
+    ("DIFFICULTY", 140.0),
+    ("SEED", 130.0),
+];
+
+pub struct LeaderboardMenuPlugin;
+
+impl Plugin for LeaderboardMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Leaderboard), spawn_leaderboard_menu)
+            .add_systems(
+                Update,
+                (
+                    LeaderboardBackButton::button_pressed_system,
+                    LeaderboardBackButton::action_pressed_system,
+                )
+                    .run_if(in_state(GameState::Leaderboard)),
+            )
+            .add_systems(OnExit(GameState::Leaderboard), despawn_leaderboard_menu);
+    }
+}
+
+#[derive(Debug, Component)]
+struct LeaderboardMenu;
+
+#[derive(Debug, Component, ChangeStateButton)]
+#[action(Back)]
+#[target_state(MainMenu)]
+struct LeaderboardBackButton;
+
+fn spawn_row(parent: &mut ChildBuilder, cells: [String; 6], color: Color) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            Name::new("LeaderboardRow"),
+        ))
+        .with_children(|parent| {
+            for (text, (_, width)) in cells.into_iter().zip(COLUMNS) {
+                parent.spawn(
+                    TextBundle::from_section(
+                        text,
+                        TextStyle {
+                            font_size: 25.0,
+                            color,
+                            ..default()
+                        },
+                    )
+                    .with_style(Style {
+                        width: Val::Px(width),
+                        ..default()
+                    }),
+                );
+            }
+        });
+}
+
+fn entry_cells(rank: usize, entry: &LeaderboardEntry, rule: &dyn ScoringRule) -> [String; 6] {
+    [
+        format!("{}", rank + 1),
+        entry.name().to_string(),
+        rule.format_score(entry.score()),
+        entry.date(),
+        format!("{:?}", entry.difficulty()),
+        format!("{:08X}", entry.seed()),
+    ]
+}
+
+//the leaderboard of the game mode picked in the settings
+fn spawn_leaderboard_menu(
+    mut commands: Commands,
+    save_data: Res<SaveData>,
+    settings: Res<Settings>,
+    run_info: Res<RunInfo>,
+    last_run_rank: Res<LastRunRank>,
+) {
+    let game_mode = settings.game_mode;
+    let rule = game_mode.scoring_rule();
+    //the last run may have been played in another mode
+    let last_run_rank = last_run_rank
+        .0
+        .filter(|_| run_info.game_mode() == game_mode);
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    height: Val::Percent(100.0),
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(50.0)),
+                    row_gap: Val::Px(10.0),
+                    ..default()
+                },
+                ..default()
+            },
+            LeaderboardMenu,
+            Name::new("LeaderboardMenu"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "LEADERBOARD",
+                    TextStyle {
+                        font_size: 50.0,
+                        ..default()
+                    },
+                ),
+                Name::new("LeaderboardTitle"),
+            ));
+            parent.spawn((
+                TextBundle::from_section(
+                    format!("{game_mode:?}").to_uppercase(),
+                    TextStyle {
+                        font_size: 25.0,
+                        color: HEADER_COLOR,
+                        ..default()
+                    },
+                ),
+                Name::new("LeaderboardGameModeLabel"),
+            ));
+
+            let entries = save_data.leaderboard(game_mode).entries();
+            if entries.is_empty() {
+                parent.spawn((
+                    TextBundle::from_section(
+                        "No runs yet",
+                        TextStyle {
+                            font_size: 25.0,
+                            ..default()
+                        },
+                    ),
+                    Name::new("EmptyLeaderboardLabel"),
+                ));
+            } else {
+                spawn_row(
+                    parent,
+                    COLUMNS.map(|(title, _)| title.to_string()),
+                    HEADER_COLOR,
+                );
+                for (rank, entry) in entries.iter().enumerate() {
+                    let color = if last_run_rank == Some(rank) {
+                        HIGHLIGHT_COLOR
+                    } else {
+                        Color::WHITE
+                    };
+                    spawn_row(parent, entry_cells(rank, entry, rule.as_ref()), color);
+                }
+            }
+
+            parent
+                .spawn(change_state_button(
+                    ButtonBundle {
+                        background_color: DEFAULT_BUTTON_COLOR.into(),
+                        style: Style {
+                            padding: UiRect::all(Val::Px(20.0)),
+                            margin: UiRect::top(Val::Px(20.0)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    LeaderboardBackButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "BACK",
+                            TextStyle {
+                                font_size: 35.0,
+                                ..default()
+                            },
+                        ),
+                        Name::new("LeaderboardBackButtonText"),
+                    ));
+                });
+        });
+}
+
+fn despawn_leaderboard_menu(mut commands: Commands, query: Query<Entity, With<LeaderboardMenu>>) {
+    for menu in query.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+}
@@ -1,5 +1,9 @@
 use crate::state::gamestate::GameState;
-use crate::ui::buttons::{change_state_button, ChangeStateButton, DEFAULT_BUTTON_COLOR};
+use crate::state::save_file::SaveData;
+use crate::state::settings::Settings;
+use crate::ui::buttons::{
+    change_state_button, Action, ChangeStateButton, DisabledButton, DEFAULT_BUTTON_COLOR,
+};
 use bevy::prelude::*;
 
 pub struct MainMenuPlugin;
@@ -8,8 +12,10 @@ pub struct MainMenuPlugin;
 fn update_systems() -> impl IntoSystemConfigs<()> {
     (
         StartGameButton::button_pressed_system,
-        StartGameButton::keyboard_pressed_system,
-        StartGameButton::gamepad_button_pressed_system,
+        LeaderboardButton::button_pressed_system,
+        StatisticsButton::button_pressed_system,
+        AchievementsButton::button_pressed_system,
+        SettingsButton::button_pressed_system,
         ExitButton::button_pressed_system,
     )
         .run_if(in_state(GameState::MainMenu))
@@ -19,8 +25,10 @@ fn update_systems() -> impl IntoSystemConfigs<()> {
 fn update_systems() -> impl IntoSystemConfigs<()> {
     (
         StartGameButton::button_pressed_system,
-        StartGameButton::keyboard_pressed_system,
-        StartGameButton::gamepad_button_pressed_system,
+        LeaderboardButton::button_pressed_system,
+        StatisticsButton::button_pressed_system,
+        AchievementsButton::button_pressed_system,
+        SettingsButton::button_pressed_system,
     )
         .run_if(in_state(GameState::MainMenu))
 }
@@ -38,15 +46,34 @@ struct MainMenu;
 
 #[derive(Debug, Component, ChangeStateButton)]
 #[target_state(Playing)]
-#[keyboard(Return)]
-#[gamepad(South)]
 struct StartGameButton;
 
+#[derive(Debug, Component, ChangeStateButton)]
+#[target_state(Leaderboard)]
+struct LeaderboardButton;
+
+#[derive(Debug, Component, ChangeStateButton)]
+#[target_state(Statistics)]
+struct StatisticsButton;
+
+#[derive(Debug, Component, ChangeStateButton)]
+#[target_state(Achievements)]
+struct AchievementsButton;
+
+#[derive(Debug, Component, ChangeStateButton)]
+#[target_state(Settings)]
+struct SettingsButton;
+
 #[derive(Debug, Component, ChangeStateButton)]
 #[target_state(Exit)]
 struct ExitButton;
 
-fn spawn_main_menu(mut commands: Commands) {
+fn spawn_main_menu(mut commands: Commands, save_data: Res<SaveData>, settings: Res<Settings>) {
+    //nothing to show until a run of the selected mode made it onto the board
+    let leaderboard_empty = save_data
+        .leaderboard(settings.game_mode)
+        .entries()
+        .is_empty();
     commands
         .spawn((
             NodeBundle {
@@ -116,6 +143,108 @@ fn spawn_main_menu(mut commands: Commands) {
                             ));
                         });
 
+                    let mut leaderboard_button = parent.spawn(change_state_button(
+                        ButtonBundle {
+                            background_color: DEFAULT_BUTTON_COLOR.into(),
+                            style: Style {
+                                padding: UiRect::all(Val::Px(20.0)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        LeaderboardButton,
+                    ));
+                    if leaderboard_empty {
+                        leaderboard_button.insert(DisabledButton);
+                    }
+                    leaderboard_button.with_children(|parent| {
+                        parent.spawn((
+                            TextBundle::from_section(
+                                "Leaderboard",
+                                TextStyle {
+                                    font_size: 20.0,
+                                    ..default()
+                                },
+                            ),
+                            Name::new("LeaderboardButtonLabel"),
+                        ));
+                    });
+
+                    parent
+                        .spawn(change_state_button(
+                            ButtonBundle {
+                                background_color: DEFAULT_BUTTON_COLOR.into(),
+                                style: Style {
+                                    padding: UiRect::all(Val::Px(20.0)),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            StatisticsButton,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    "Statistics",
+                                    TextStyle {
+                                        font_size: 20.0,
+                                        ..default()
+                                    },
+                                ),
+                                Name::new("StatisticsButtonLabel"),
+                            ));
+                        });
+
+                    parent
+                        .spawn(change_state_button(
+                            ButtonBundle {
+                                background_color: DEFAULT_BUTTON_COLOR.into(),
+                                style: Style {
+                                    padding: UiRect::all(Val::Px(20.0)),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            AchievementsButton,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    "Achievements",
+                                    TextStyle {
+                                        font_size: 20.0,
+                                        ..default()
+                                    },
+                                ),
+                                Name::new("AchievementsButtonLabel"),
+                            ));
+                        });
+
+                    parent
+                        .spawn(change_state_button(
+                            ButtonBundle {
+                                background_color: DEFAULT_BUTTON_COLOR.into(),
+                                style: Style {
+                                    padding: UiRect::all(Val::Px(20.0)),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            SettingsButton,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    "Settings",
+                                    TextStyle {
+                                        font_size: 20.0,
+                                        ..default()
+                                    },
+                                ),
+                                Name::new("SettingsButtonLabel"),
+                            ));
+                        });
+
                     if !cfg!(target_family = "wasm") {
                         parent
                             .spawn(change_state_button(
@@ -1,19 +1,40 @@
+mod achievements_menu;
 mod buttons;
+mod controls_menu;
+mod fps_counter;
 mod fullscreen;
 mod game_over_dialog;
+mod leaderboard_menu;
 mod main_menu;
+mod name_entry;
 mod pause_menu;
+pub mod prompts;
+mod score_popups;
 mod scoreboard;
+mod settings_menu;
+mod statistics_menu;
+pub mod toasts;
 
 pub struct UiPlugin;
 
 impl bevy::app::PluginGroup for UiPlugin {
     fn build(self) -> bevy::app::PluginGroupBuilder {
         bevy::app::PluginGroupBuilder::start::<Self>()
+            .add(buttons::ButtonsPlugin)
             .add(game_over_dialog::GameOverDialogPlugin)
             .add(scoreboard::ScoreBoardPlugin)
+            .add(score_popups::ScorePopupsPlugin)
             .add(main_menu::MainMenuPlugin)
             .add(pause_menu::PauseMenuPlugin)
             .add(fullscreen::FullScreenPlugin)
+            .add(settings_menu::SettingsMenuPlugin)
+            .add(controls_menu::ControlsMenuPlugin)
+            .add(leaderboard_menu::LeaderboardMenuPlugin)
+            .add(name_entry::NameEntryPlugin)
+            .add(statistics_menu::StatisticsMenuPlugin)
+            .add(achievements_menu::AchievementsMenuPlugin)
+            .add(fps_counter::FpsCounterPlugin)
+            .add(prompts::PromptsPlugin)
+            .add(toasts::ToastsPlugin)
     }
 }
@@ -0,0 +1,311 @@
+use bevy::prelude::*;
+use bevy::window::ReceivedCharacter;
+
+use super::buttons::{focusable_button, SelectedButton};
+use crate::consts::{DEFAULT_PLAYER_NAME, MAX_PLAYER_NAME_LENGTH};
+use crate::input::{action_just_pressed, Action, ActionInput, TextInput};
+use crate::state::gamescore::GameScore;
+use crate::state::gamestate::GameState;
+use crate::state::save_file::SaveData;
+
+//the on-screen keyboard, followed by a row with space, delete and done
+const KEY_ROWS: [&str; 4] = ["ABCDEFGHIJ", "KLMNOPQRST", "UVWXYZ0123", "456789-._"];
+const KEY_SIZE: f32 = 50.0;
+
+pub struct NameEntryPlugin;
+
+impl Plugin for NameEntryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnteredName>()
+            .add_systems(
+                OnEnter(GameState::NameEntry),
+                (start_name_entry, spawn_name_entry),
+            )
+            .add_systems(
+                Update,
+                (
+                    type_name,
+                    name_key_pressed,
+                    delete_character.run_if(action_just_pressed(Action::Back)),
+                    update_name_label.run_if(resource_changed::<EnteredName>()),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::NameEntry)),
+            )
+            .add_systems(
+                OnExit(GameState::NameEntry),
+                (despawn_name_entry, stop_name_entry),
+            );
+    }
+}
+
+#[derive(Debug, Resource, Default)]
+struct EnteredName(String);
+
+impl EnteredName {
+    //the UI font only has glyphs for ASCII
+    fn push(&mut self, character: char) {
+        let allowed = character.is_ascii_alphanumeric() || " -._".contains(character);
+        if allowed && self.0.len() < MAX_PLAYER_NAME_LENGTH {
+            self.0.push(character);
+        }
+    }
+
+    fn pop(&mut self) {
+        self.0.pop();
+    }
+
+    fn label(&self) -> String {
+        if self.0.len() < MAX_PLAYER_NAME_LENGTH {
+            format!("{}_", self.0)
+        } else {
+            self.0.clone()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NameKey {
+    Character(char),
+    Delete,
+    Done,
+}
+
+#[derive(Debug, Component)]
+struct NameKeyButton(NameKey);
+
+#[derive(Debug, Component)]
+struct NameEntryDialog;
+
+#[derive(Debug, Component)]
+struct NameLabel;
+
+fn start_name_entry(
+    save_data: Res<SaveData>,
+    mut entered_name: ResMut<EnteredName>,
+    mut text_input: ResMut<TextInput>,
+) {
+    entered_name.0 = save_data.player_name().to_string();
+    text_input.0 = true;
+}
+
+fn stop_name_entry(mut text_input: ResMut<TextInput>) {
+    text_input.0 = false;
+}
+
+fn spawn_name_key(parent: &mut ChildBuilder, key: NameKey, text: &str, width: f32) {
+    parent
+        .spawn((
+            focusable_button(
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Px(width),
+                        height: Val::Px(KEY_SIZE),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                format!("{key:?}NameKey"),
+            ),
+            NameKeyButton(key),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font_size: 25.0,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn spawn_key_row(parent: &mut ChildBuilder, keys: impl FnOnce(&mut ChildBuilder)) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    column_gap: Val::Px(8.0),
+                    ..default()
+                },
+                ..default()
+            },
+            Name::new("NameKeyRow"),
+        ))
+        .with_children(keys);
+}
+
+fn spawn_name_entry(
+    mut commands: Commands,
+    game_score: Res<GameScore>,
+    entered_name: Res<EnteredName>,
+) {
+    let score = game_score.get_current_score();
+    //plain points have no units to show
+    let score = match game_score.units() {
+        "" => format!("{score} POINTS"),
+        _ => game_score.format_score(score).to_uppercase(),
+    };
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    height: Val::Percent(100.0),
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(50.0)),
+                    row_gap: Val::Px(8.0),
+                    ..default()
+                },
+                ..default()
+            },
+            NameEntryDialog,
+            Name::new("NameEntryDialog"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    format!("{score}, A NEW RECORD!"),
+                    TextStyle {
+                        font_size: 50.0,
+                        ..default()
+                    },
+                ),
+                Name::new("NameEntryTitle"),
+            ));
+            parent.spawn((
+                TextBundle::from_section(
+                    "Enter your name",
+                    TextStyle {
+                        font_size: 25.0,
+                        ..default()
+                    },
+                ),
+                Name::new("NameEntryHint"),
+            ));
+            parent.spawn((
+                TextBundle::from_section(
+                    entered_name.label(),
+                    TextStyle {
+                        font_size: 45.0,
+                        color: Color::GOLD,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::vertical(Val::Px(15.0)),
+                    ..default()
+                }),
+                NameLabel,
+                Name::new("NameLabel"),
+            ));
+
+            for row in KEY_ROWS {
+                spawn_key_row(parent, |parent| {
+                    for character in row.chars() {
+                        spawn_name_key(
+                            parent,
+                            NameKey::Character(character),
+                            &character.to_string(),
+                            KEY_SIZE,
+                        );
+                    }
+                });
+            }
+            spawn_key_row(parent, |parent| {
+                spawn_name_key(parent, NameKey::Character(' '), "SPACE", 170.0);
+                spawn_name_key(parent, NameKey::Delete, "DEL", 110.0);
+                spawn_name_key(parent, NameKey::Done, "OK", 110.0);
+            });
+        });
+}
+
+fn finish_name_entry(
+    entered_name: &EnteredName,
+    save_data: &mut SaveData,
+    next_state: &mut NextState<GameState>,
+) {
+    let name = entered_name.0.trim();
+    let name = if name.is_empty() {
+        DEFAULT_PLAYER_NAME
+    } else {
+        name
+    };
+    save_data.set_player_name(name.to_string());
+    next_state.set(GameState::GameOver);
+}
+
+//A physical keyboard types directly. Characters from before the dialog
+//opened are dropped, so the key that ended the run is not typed into the name.
+fn type_name(
+    game_state: Res<State<GameState>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    keyboard: Res<Input<KeyCode>>,
+    mut entered_name: ResMut<EnteredName>,
+    mut save_data: ResMut<SaveData>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if game_state.is_changed() {
+        characters.clear();
+    }
+    for event in characters.read() {
+        entered_name.push(event.char);
+    }
+    if keyboard.just_pressed(KeyCode::Back) {
+        entered_name.pop();
+    }
+    if keyboard.any_just_pressed([KeyCode::Return, KeyCode::NumpadEnter]) {
+        finish_name_entry(&entered_name, &mut save_data, &mut next_state);
+    }
+}
+
+//a click or touch on a key, or Confirm while it has focus
+fn name_key_pressed(
+    interaction_query: Query<(&Interaction, &NameKeyButton), Changed<Interaction>>,
+    query_keys: Query<&NameKeyButton>,
+    selected_button: Res<SelectedButton>,
+    input: ActionInput,
+    mut entered_name: ResMut<EnteredName>,
+    mut save_data: ResMut<SaveData>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let clicked = interaction_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, key)| key.0);
+    let confirmed = selected_button
+        .entity()
+        .filter(|_| input.just_pressed(Action::Confirm))
+        .and_then(|entity| query_keys.get(entity).ok())
+        .map(|key| key.0);
+
+    for key in clicked.chain(confirmed) {
+        match key {
+            NameKey::Character(character) => entered_name.push(character),
+            NameKey::Delete => entered_name.pop(),
+            NameKey::Done => finish_name_entry(&entered_name, &mut save_data, &mut next_state),
+        }
+    }
+}
+
+fn delete_character(mut entered_name: ResMut<EnteredName>) {
+    entered_name.pop();
+}
+
+fn update_name_label(
+    entered_name: Res<EnteredName>,
+    mut query_label: Query<&mut Text, With<NameLabel>>,
+) {
+    for mut text in query_label.iter_mut() {
+        let section = text.sections.first_mut().expect("to have a TextSection");
+        section.value = entered_name.label();
+    }
+}
+
+fn despawn_name_entry(mut commands: Commands, query: Query<Entity, With<NameEntryDialog>>) {
+    for dialog in query.iter() {
+        commands.entity(dialog).despawn_recursive();
+    }
+}
@@ -1,5 +1,7 @@
+use crate::input::TouchControls;
 use crate::state::gamestate::GameState;
-use crate::ui::buttons::{change_state_button, ChangeStateButton, DEFAULT_BUTTON_COLOR};
+use crate::state::pause_reason::PauseReason;
+use crate::ui::buttons::{change_state_button, Action, ChangeStateButton, DEFAULT_BUTTON_COLOR};
 use bevy::prelude::*;
 
 pub struct PauseMenuPlugin;
@@ -9,17 +11,21 @@ impl Plugin for PauseMenuPlugin {
         app.add_systems(
             Update,
             (
-                PauseButton::keyboard_pressed_system,
-                PauseButton::gamepad_button_pressed_system,
+                PauseButton::action_pressed_system,
+                TouchPauseButton::button_pressed_system,
+                show_touch_pause_button.run_if(resource_changed::<TouchControls>()),
             )
                 .run_if(in_state(GameState::Playing)),
         )
+        .add_systems(OnEnter(GameState::Playing), spawn_touch_pause_button)
+        .add_systems(OnExit(GameState::Playing), despawn_touch_pause_button)
         .add_systems(
             Update,
             (
                 UnpauseButton::button_pressed_system,
-                UnpauseButton::keyboard_pressed_system,
-                UnpauseButton::gamepad_button_pressed_system,
+                UnpauseButton::action_pressed_system,
+                SettingsButton::button_pressed_system,
+                update_pause_reason_label.run_if(resource_changed::<PauseReason>()),
             )
                 .run_if(in_state(GameState::Pause)),
         )
@@ -31,19 +37,29 @@ impl Plugin for PauseMenuPlugin {
 #[derive(Debug, Component)]
 struct PauseMenu;
 
+#[derive(Debug, Component)]
+struct PauseReasonLabel;
+
 #[derive(Debug, Component, ChangeStateButton)]
 #[target_state(Pause)]
-#[keyboard(Escape)]
-#[gamepad(East)]
+#[action(Pause)]
 struct PauseButton;
 
+//shown on touch screens, where there is no key to pause with
+#[derive(Debug, Component, ChangeStateButton)]
+#[target_state(Pause)]
+struct TouchPauseButton;
+
 #[derive(Debug, Component, ChangeStateButton)]
 #[target_state(Playing)]
-#[keyboard(Escape, Return)]
-#[gamepad(East, North)]
+#[action(Back)]
 struct UnpauseButton;
 
-fn spawn_game_pause_menu(mut commands: Commands) {
+#[derive(Debug, Component, ChangeStateButton)]
+#[target_state(Settings)]
+struct SettingsButton;
+
+fn spawn_game_pause_menu(mut commands: Commands, pause_reason: Res<PauseReason>) {
     commands
         .spawn((
             NodeBundle {
@@ -76,6 +92,22 @@ fn spawn_game_pause_menu(mut commands: Commands) {
                 ),
                 Name::new("PauseDialogText"),
             ));
+            parent.spawn((
+                TextBundle::from_section(
+                    pause_reason.message().unwrap_or_default(),
+                    TextStyle {
+                        font_size: 25.0,
+                        color: Color::YELLOW,
+                        ..default()
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::vertical(Val::Px(20.0)),
+                    ..default()
+                }),
+                PauseReasonLabel,
+                Name::new("PauseReasonLabel"),
+            ));
             parent
                 .spawn(change_state_button(
                     ButtonBundle {
@@ -100,11 +132,107 @@ fn spawn_game_pause_menu(mut commands: Commands) {
                         Name::new("UnpauseButtonText"),
                     ));
                 });
+            parent
+                .spawn(change_state_button(
+                    ButtonBundle {
+                        background_color: DEFAULT_BUTTON_COLOR.into(),
+                        style: Style {
+                            padding: UiRect::all(Val::Px(20.0)),
+                            margin: UiRect::top(Val::Px(20.0)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    SettingsButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "SETTINGS",
+                            TextStyle {
+                                font_size: 35.0,
+                                ..default()
+                            },
+                        ),
+                        Name::new("SettingsButtonText"),
+                    ));
+                });
         });
 }
 
+fn update_pause_reason_label(
+    pause_reason: Res<PauseReason>,
+    mut query_label: Query<&mut Text, With<PauseReasonLabel>>,
+) {
+    for mut text in query_label.iter_mut() {
+        let section = text.sections.first_mut().expect("to have a TextSection");
+        section.value = pause_reason.message().unwrap_or_default().to_string();
+    }
+}
+
 fn despawn_game_pause_menu(mut commands: Commands, dialog_query: Query<Entity, With<PauseMenu>>) {
     for dialog in dialog_query.iter() {
         commands.entity(dialog).despawn_recursive();
     }
 }
+
+fn spawn_touch_pause_button(mut commands: Commands, touch_controls: Res<TouchControls>) {
+    commands
+        .spawn(change_state_button(
+            ButtonBundle {
+                background_color: DEFAULT_BUTTON_COLOR.into(),
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(20.0),
+                    left: Val::Px(20.0),
+                    width: Val::Px(80.0),
+                    height: Val::Px(80.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                visibility: touch_pause_button_visibility(&touch_controls),
+                z_index: ZIndex::Global(1),
+                ..default()
+            },
+            TouchPauseButton,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "II",
+                    TextStyle {
+                        font_size: 40.0,
+                        ..default()
+                    },
+                ),
+                Name::new("TouchPauseButtonText"),
+            ));
+        });
+}
+
+fn touch_pause_button_visibility(touch_controls: &TouchControls) -> Visibility {
+    if touch_controls.visible {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+fn show_touch_pause_button(
+    touch_controls: Res<TouchControls>,
+    mut query_button: Query<&mut Visibility, With<TouchPauseButton>>,
+) {
+    for mut visibility in query_button.iter_mut() {
+        visibility.set_if_neq(touch_pause_button_visibility(&touch_controls));
+    }
+}
+
+fn despawn_touch_pause_button(
+    mut commands: Commands,
+    button_query: Query<Entity, With<TouchPauseButton>>,
+) {
+    for button in button_query.iter() {
+        commands.entity(button).despawn_recursive();
+    }
+}
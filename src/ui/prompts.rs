@@ -0,0 +1,301 @@
+use bevy::prelude::*;
+
+use crate::input::{Action, ActionMap, Binding, LastInputDevice};
+
+const GLYPH_SIZE: f32 = 30.0;
+
+pub struct PromptsPlugin;
+
+impl Plugin for PromptsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, update_prompts);
+    }
+}
+
+//Shows the glyphs of the bindings for the actions on the last used device.
+//The node's children are replaced whenever the glyphs change.
+#[derive(Debug, Component, Clone, PartialEq, Eq)]
+pub struct ActionPrompt(pub Vec<Action>);
+
+pub fn action_prompt(actions: Vec<Action>) -> impl Bundle {
+    (
+        NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(5.0),
+                ..default()
+            },
+            ..default()
+        },
+        ActionPrompt(actions),
+        Name::new("ActionPrompt"),
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GamepadStyle {
+    Xbox,
+    PlayStation,
+}
+
+impl GamepadStyle {
+    fn detect(name: Option<&str>) -> GamepadStyle {
+        let name = name.unwrap_or_default().to_lowercase();
+        let playstation = [
+            "playstation",
+            "dualshock",
+            "dualsense",
+            "sony",
+            "ps3",
+            "ps4",
+            "ps5",
+        ];
+        if playstation.iter().any(|part| name.contains(part)) {
+            GamepadStyle::PlayStation
+        } else {
+            GamepadStyle::Xbox
+        }
+    }
+}
+
+//the binding shown for an action, preferring keys over the mouse
+fn binding_for_device(
+    action_map: &ActionMap,
+    action: Action,
+    device: LastInputDevice,
+) -> Option<Binding> {
+    let bindings = action_map.bindings(action);
+    let find = |matches: fn(&Binding) -> bool| bindings.iter().copied().find(matches);
+    match device {
+        LastInputDevice::KeyboardMouse => find(|binding| matches!(binding, Binding::Key(_)))
+            .or_else(|| find(|binding| matches!(binding, Binding::Mouse(_)))),
+        LastInputDevice::Gamepad(_) => find(|binding| matches!(binding, Binding::Gamepad(_))),
+        LastInputDevice::Touch => {
+            find(|binding| matches!(binding, Binding::Touch | Binding::Swipe))
+        }
+    }
+}
+
+fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Return => "Enter".to_string(),
+        KeyCode::Escape => "Esc".to_string(),
+        KeyCode::Back => "Backspace".to_string(),
+        KeyCode::Key0 => "0".to_string(),
+        KeyCode::Key1 => "1".to_string(),
+        KeyCode::Key2 => "2".to_string(),
+        KeyCode::Key3 => "3".to_string(),
+        KeyCode::Key4 => "4".to_string(),
+        KeyCode::Key5 => "5".to_string(),
+        KeyCode::Key6 => "6".to_string(),
+        KeyCode::Key7 => "7".to_string(),
+        KeyCode::Key8 => "8".to_string(),
+        KeyCode::Key9 => "9".to_string(),
+        key => format!("{key:?}"),
+    }
+}
+
+fn spawn_key_cap(parent: &mut ChildBuilder, text: String) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                min_width: Val::Px(GLYPH_SIZE),
+                height: Val::Px(GLYPH_SIZE),
+                padding: UiRect::horizontal(Val::Px(6.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::rgb(0.15, 0.15, 0.15).into(),
+            border_color: Color::WHITE.into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font_size: 18.0,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn spawn_image_glyph(
+    parent: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    path: &str,
+    color: Color,
+    children: impl FnOnce(&mut ChildBuilder),
+) {
+    parent
+        .spawn(ImageBundle {
+            image: UiImage::new(asset_server.load(path.to_string())),
+            background_color: color.into(),
+            style: Style {
+                width: Val::Px(GLYPH_SIZE),
+                height: Val::Px(GLYPH_SIZE),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(children);
+}
+
+fn spawn_face_button(
+    parent: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    button: GamepadButtonType,
+    style: GamepadStyle,
+) {
+    match style {
+        GamepadStyle::Xbox => {
+            let (letter, color) = match button {
+                GamepadButtonType::South => ("A", Color::rgb(0.2, 0.7, 0.2)),
+                GamepadButtonType::East => ("B", Color::rgb(0.8, 0.2, 0.2)),
+                GamepadButtonType::West => ("X", Color::rgb(0.2, 0.4, 0.9)),
+                _ => ("Y", Color::rgb(0.9, 0.75, 0.1)),
+            };
+            spawn_image_glyph(
+                parent,
+                asset_server,
+                "prompts/face-button.png",
+                color,
+                |parent| {
+                    parent.spawn(TextBundle::from_section(
+                        letter,
+                        TextStyle {
+                            font_size: 20.0,
+                            ..default()
+                        },
+                    ));
+                },
+            );
+        }
+        GamepadStyle::PlayStation => {
+            let (shape, color) = match button {
+                GamepadButtonType::South => ("prompts/ps-cross.png", Color::rgb(0.5, 0.6, 1.0)),
+                GamepadButtonType::East => ("prompts/ps-circle.png", Color::rgb(1.0, 0.4, 0.4)),
+                GamepadButtonType::West => ("prompts/ps-square.png", Color::rgb(1.0, 0.5, 0.8)),
+                _ => ("prompts/ps-triangle.png", Color::rgb(0.3, 0.9, 0.7)),
+            };
+            spawn_image_glyph(
+                parent,
+                asset_server,
+                "prompts/face-button.png",
+                Color::rgb(0.15, 0.15, 0.15),
+                |parent| {
+                    parent.spawn(ImageBundle {
+                        image: UiImage::new(asset_server.load(shape)),
+                        background_color: color.into(),
+                        style: Style {
+                            width: Val::Percent(70.0),
+                            height: Val::Percent(70.0),
+                            ..default()
+                        },
+                        ..default()
+                    });
+                },
+            );
+        }
+    }
+}
+
+fn gamepad_button_name(button: GamepadButtonType, style: GamepadStyle) -> String {
+    let xbox = style == GamepadStyle::Xbox;
+    match button {
+        GamepadButtonType::LeftTrigger => (if xbox { "LB" } else { "L1" }).to_string(),
+        GamepadButtonType::RightTrigger => (if xbox { "RB" } else { "R1" }).to_string(),
+        GamepadButtonType::LeftTrigger2 => (if xbox { "LT" } else { "L2" }).to_string(),
+        GamepadButtonType::RightTrigger2 => (if xbox { "RT" } else { "R2" }).to_string(),
+        GamepadButtonType::Select => (if xbox { "View" } else { "Share" }).to_string(),
+        GamepadButtonType::Start => (if xbox { "Menu" } else { "Options" }).to_string(),
+        GamepadButtonType::LeftThumb => (if xbox { "LS" } else { "L3" }).to_string(),
+        GamepadButtonType::RightThumb => (if xbox { "RS" } else { "R3" }).to_string(),
+        GamepadButtonType::DPadUp => "D-Pad Up".to_string(),
+        GamepadButtonType::DPadDown => "D-Pad Down".to_string(),
+        GamepadButtonType::DPadLeft => "D-Pad Left".to_string(),
+        GamepadButtonType::DPadRight => "D-Pad Right".to_string(),
+        button => format!("{button:?}"),
+    }
+}
+
+fn spawn_glyph(
+    parent: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    binding: Binding,
+    style: GamepadStyle,
+) {
+    match binding {
+        Binding::Key(key) => spawn_key_cap(parent, key_name(key)),
+        Binding::Mouse(MouseButton::Left) => spawn_key_cap(parent, "LMB".to_string()),
+        Binding::Mouse(MouseButton::Right) => spawn_key_cap(parent, "RMB".to_string()),
+        Binding::Mouse(MouseButton::Middle) => spawn_key_cap(parent, "MMB".to_string()),
+        Binding::Mouse(button) => spawn_key_cap(parent, format!("Mouse {button:?}")),
+        Binding::Gamepad(
+            button @ (GamepadButtonType::South
+            | GamepadButtonType::East
+            | GamepadButtonType::West
+            | GamepadButtonType::North),
+        ) => spawn_face_button(parent, asset_server, button, style),
+        Binding::Gamepad(button) => spawn_key_cap(parent, gamepad_button_name(button, style)),
+        Binding::Touch => spawn_image_glyph(
+            parent,
+            asset_server,
+            "prompts/touch.png",
+            Color::WHITE,
+            |_| {},
+        ),
+        Binding::Swipe => spawn_key_cap(parent, "Swipe".to_string()),
+    }
+}
+
+fn update_prompts(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    last_device: Res<LastInputDevice>,
+    action_map: Res<ActionMap>,
+    gamepads: Res<Gamepads>,
+    mut query_prompts: Query<(Entity, Ref<ActionPrompt>, &mut Style)>,
+) {
+    let refresh_all = last_device.is_changed() || action_map.is_changed();
+    let style = match *last_device {
+        LastInputDevice::Gamepad(gamepad) => GamepadStyle::detect(gamepads.name(gamepad)),
+        _ => GamepadStyle::Xbox,
+    };
+
+    for (entity, prompt, mut node_style) in query_prompts.iter_mut() {
+        if !refresh_all && !prompt.is_changed() {
+            continue;
+        }
+
+        let mut bindings: Vec<Binding> = Vec::new();
+        for action in prompt.0.iter() {
+            if let Some(binding) = binding_for_device(&action_map, *action, *last_device) {
+                if !bindings.contains(&binding) {
+                    bindings.push(binding);
+                }
+            }
+        }
+
+        let display = if bindings.is_empty() {
+            Display::None
+        } else {
+            Display::Flex
+        };
+        if node_style.display != display {
+            node_style.display = display;
+        }
+        commands
+            .entity(entity)
+            .despawn_descendants()
+            .with_children(|parent| {
+                for binding in bindings {
+                    spawn_glyph(parent, &asset_server, binding, style);
+                }
+            });
+    }
+}
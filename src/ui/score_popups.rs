@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use crate::state::bonus_scoring::{BonusKind, BonusScored};
+use crate::state::gamestate::GameState;
+use crate::state::settings::Settings;
+
+const POPUP_SECONDS: f32 = 1.0;
+const POPUP_RISE_SPEED: f32 = 80.0;
+//above the moai and the pebble
+const POPUP_Z: f32 = 10.0;
+
+pub struct ScorePopupsPlugin;
+
+impl Plugin for ScorePopupsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (spawn_score_popups, animate_score_popups).chain())
+            .add_systems(OnEnter(GameState::MainMenu), despawn_score_popups);
+    }
+}
+
+//floats up from where the bonus was scored and fades out
+#[derive(Debug, Component)]
+struct ScorePopup {
+    age: f32,
+}
+
+fn popup_color(kind: BonusKind) -> Color {
+    match kind {
+        BonusKind::NearMiss => Color::ORANGE,
+        BonusKind::Centre => Color::CYAN,
+    }
+}
+
+fn spawn_score_popups(mut commands: Commands, mut bonus_events: EventReader<BonusScored>) {
+    for bonus in bonus_events.read() {
+        let mut text = format!("+{} {}", bonus.points, bonus.kind.label());
+        if bonus.multiplier > 1 {
+            text += &format!(" x{}", bonus.multiplier);
+        }
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    text,
+                    TextStyle {
+                        font_size: 30.0,
+                        color: popup_color(bonus.kind),
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_translation(bonus.position.extend(POPUP_Z)),
+                ..default()
+            },
+            ScorePopup { age: 0.0 },
+            Name::new("ScorePopup"),
+        ));
+    }
+}
+
+fn animate_score_popups(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut query_popups: Query<(Entity, &mut ScorePopup, &mut Transform, &mut Text)>,
+) {
+    for (entity, mut popup, mut transform, mut text) in query_popups.iter_mut() {
+        popup.age += time.delta_seconds();
+        if popup.age >= POPUP_SECONDS {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        if settings.reduced_motion {
+            continue;
+        }
+
+        transform.translation.y += POPUP_RISE_SPEED * time.delta_seconds();
+        let alpha = 1.0 - popup.age / POPUP_SECONDS;
+        for section in text.sections.iter_mut() {
+            section.style.color.set_a(alpha);
+        }
+    }
+}
+
+fn despawn_score_popups(mut commands: Commands, query_popups: Query<Entity, With<ScorePopup>>) {
+    for popup in query_popups.iter() {
+        commands.entity(popup).despawn_recursive();
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
-use crate::state::{gamescore::GameScore, gamestate::GameState};
+use crate::state::gamescore::{start_scoring, GameScore};
+use crate::state::gamestate::GameState;
 
 pub struct ScoreBoardPlugin;
 
@@ -11,20 +12,20 @@ impl Plugin for ScoreBoardPlugin {
                 from: GameState::MainMenu,
                 to: GameState::Playing,
             },
-            spawn_scoreboard,
+            spawn_scoreboard.after(start_scoring),
         )
         .add_systems(
             OnTransition {
                 from: GameState::GameOver,
                 to: GameState::Playing,
             },
-            spawn_scoreboard,
+            spawn_scoreboard.after(start_scoring),
         )
         .add_systems(OnEnter(GameState::MainMenu), despawn_scoreboard)
         .add_systems(OnEnter(GameState::GameOver), despawn_scoreboard)
         .add_systems(
             Update,
-            (update_scoreboard, update_is_highscore_label).run_if(in_state(GameState::Playing)),
+            update_scoreboard.run_if(in_state(GameState::Playing)),
         );
     }
 }
@@ -35,10 +36,7 @@ struct ScoreBoard;
 #[derive(Debug, Component)]
 struct ScoreLabel;
 
-#[derive(Debug, Component)]
-struct IsHighScoreLabel;
-
-fn spawn_scoreboard(mut commands: Commands) {
+fn spawn_scoreboard(mut commands: Commands, game_score: Res<GameScore>) {
     commands
         .spawn((
             NodeBundle {
@@ -69,7 +67,7 @@ fn spawn_scoreboard(mut commands: Commands) {
                 .with_children(|parent| {
                     parent.spawn((
                         TextBundle::from_section(
-                            "Score: ",
+                            format!("{}: ", game_score.label()),
                             TextStyle {
                                 font_size: 20.0,
                                 ..default()
@@ -89,18 +87,6 @@ fn spawn_scoreboard(mut commands: Commands) {
                         Name::new("ScoreLabel"),
                     ));
                 });
-
-            parent.spawn((
-                TextBundle::from_section(
-                    "",
-                    TextStyle {
-                        font_size: 20.0,
-                        color: Color::RED,
-                        ..default()
-                    },
-                ),
-                IsHighScoreLabel,
-            ));
         });
 }
 
@@ -122,24 +108,5 @@ fn update_scoreboard(
     }
     let mut text = text.unwrap();
     let section = text.sections.first_mut().expect("to have a TextSection");
-    section.value = game_score.get_current_score().to_string();
-}
-
-fn update_is_highscore_label(
-    game_score: Res<GameScore>,
-    mut query_is_highscore_label: Query<&mut Text, With<IsHighScoreLabel>>,
-) {
-    let text = query_is_highscore_label.get_single_mut();
-    if let Err(error) = text {
-        debug!("{}", error);
-        return;
-    }
-    let mut text = text.unwrap();
-    let section = text.sections.first_mut().expect("to have a TextSection");
-    section.value = (if game_score.is_new_high_score() {
-        "Highscore!"
-    } else {
-        ""
-    })
-    .to_string();
+    section.value = game_score.format_score(game_score.get_current_score());
 }
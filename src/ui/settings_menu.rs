@@ -0,0 +1,422 @@
+use bevy::prelude::*;
+
+use crate::input::{action_just_pressed, ActionInput};
+use crate::state::gamestate::GameState;
+use crate::state::settings::{Settings, SettingsOrigin};
+use crate::ui::buttons::{
+    change_state_button, focusable_button, Action, ChangeStateButton, SelectedButton,
+    DEFAULT_BUTTON_COLOR,
+};
+
+const PERCENT_STEP: f32 = 0.1;
+
+pub struct SettingsMenuPlugin;
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Settings), spawn_settings_menu)
+            .add_systems(
+                Update,
+                (
+                    setting_button_pressed,
+                    update_setting_labels.run_if(resource_changed::<Settings>()),
+                    back_button_pressed,
+                    ControlsButton::button_pressed_system,
+                    go_back.run_if(action_just_pressed(Action::Back)),
+                )
+                    .run_if(in_state(GameState::Settings)),
+            )
+            .add_systems(OnExit(GameState::Settings), despawn_settings_menu);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SettingKind {
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    DisplayMode,
+    VSync,
+    ShowFps,
+    ReducedMotion,
+    Difficulty,
+    GameMode,
+    BonusScoring,
+    ResumeCountdown,
+    Rumble,
+    FlapRumble,
+    ScoreRumble,
+    DeathRumble,
+}
+
+impl SettingKind {
+    fn all() -> Vec<SettingKind> {
+        let mut kinds = vec![
+            SettingKind::MasterVolume,
+            SettingKind::MusicVolume,
+            SettingKind::SfxVolume,
+        ];
+        //the browser owns the window, so there is nothing to configure there
+        if cfg!(not(target_family = "wasm")) {
+            kinds.push(SettingKind::DisplayMode);
+            kinds.push(SettingKind::VSync);
+        }
+        kinds.push(SettingKind::ShowFps);
+        kinds.push(SettingKind::ReducedMotion);
+        kinds.push(SettingKind::Difficulty);
+        kinds.push(SettingKind::GameMode);
+        kinds.push(SettingKind::BonusScoring);
+        kinds.push(SettingKind::ResumeCountdown);
+        //browsers can't rumble gamepads
+        if cfg!(not(target_family = "wasm")) {
+            kinds.push(SettingKind::Rumble);
+            kinds.push(SettingKind::FlapRumble);
+            kinds.push(SettingKind::ScoreRumble);
+            kinds.push(SettingKind::DeathRumble);
+        }
+        kinds
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SettingKind::MasterVolume => "Master volume",
+            SettingKind::MusicVolume => "Music volume",
+            SettingKind::SfxVolume => "Sound effects volume",
+            SettingKind::DisplayMode => "Window mode",
+            SettingKind::VSync => "VSync",
+            SettingKind::ShowFps => "Show FPS",
+            SettingKind::ReducedMotion => "Reduced motion",
+            SettingKind::Difficulty => "Difficulty",
+            SettingKind::GameMode => "Game mode",
+            SettingKind::BonusScoring => "Bonus scoring (unranked)",
+            SettingKind::ResumeCountdown => "Resume countdown",
+            SettingKind::Rumble => "Rumble",
+            SettingKind::FlapRumble => "Flap rumble",
+            SettingKind::ScoreRumble => "Score rumble",
+            SettingKind::DeathRumble => "Crash rumble",
+        }
+    }
+
+    fn is_percentage(self) -> bool {
+        matches!(
+            self,
+            SettingKind::MasterVolume
+                | SettingKind::MusicVolume
+                | SettingKind::SfxVolume
+                | SettingKind::FlapRumble
+                | SettingKind::ScoreRumble
+                | SettingKind::DeathRumble
+        )
+    }
+
+    fn value(self, settings: &Settings) -> String {
+        fn on_off(value: bool) -> String {
+            (if value { "On" } else { "Off" }).to_string()
+        }
+        fn percent(value: f32) -> String {
+            format!("{}%", (value * 100.0).round())
+        }
+
+        match self {
+            SettingKind::MasterVolume => percent(settings.master_volume),
+            SettingKind::MusicVolume => percent(settings.music_volume),
+            SettingKind::SfxVolume => percent(settings.sfx_volume),
+            SettingKind::DisplayMode => format!("{:?}", settings.display_mode),
+            SettingKind::VSync => on_off(settings.vsync),
+            SettingKind::ShowFps => on_off(settings.show_fps),
+            SettingKind::ReducedMotion => on_off(settings.reduced_motion),
+            SettingKind::Difficulty => format!("{:?}", settings.difficulty),
+            SettingKind::GameMode => format!("{:?}", settings.game_mode),
+            SettingKind::BonusScoring => on_off(settings.bonus_scoring),
+            SettingKind::ResumeCountdown => match settings.resume_countdown.seconds() {
+                seconds if seconds > 0.0 => format!("{seconds}s"),
+                _ => "Off".to_string(),
+            },
+            SettingKind::Rumble => on_off(settings.rumble),
+            SettingKind::FlapRumble => percent(settings.flap_rumble),
+            SettingKind::ScoreRumble => percent(settings.score_rumble),
+            SettingKind::DeathRumble => percent(settings.death_rumble),
+        }
+    }
+
+    fn change(self, settings: &mut Settings, step: f32) {
+        fn change_percentage(value: &mut f32, step: f32) {
+            *value = ((*value + step * PERCENT_STEP) * 10.0)
+                .round()
+                .clamp(0.0, 10.0)
+                / 10.0;
+        }
+
+        match self {
+            SettingKind::MasterVolume => change_percentage(&mut settings.master_volume, step),
+            SettingKind::MusicVolume => change_percentage(&mut settings.music_volume, step),
+            SettingKind::SfxVolume => change_percentage(&mut settings.sfx_volume, step),
+            SettingKind::DisplayMode => settings.display_mode = settings.display_mode.next(),
+            SettingKind::VSync => settings.vsync = !settings.vsync,
+            SettingKind::ShowFps => settings.show_fps = !settings.show_fps,
+            SettingKind::ReducedMotion => settings.reduced_motion = !settings.reduced_motion,
+            SettingKind::Difficulty => settings.difficulty = settings.difficulty.next(),
+            SettingKind::GameMode => settings.game_mode = settings.game_mode.next(),
+            SettingKind::BonusScoring => settings.bonus_scoring = !settings.bonus_scoring,
+            SettingKind::ResumeCountdown => {
+                settings.resume_countdown = settings.resume_countdown.next()
+            }
+            SettingKind::Rumble => settings.rumble = !settings.rumble,
+            SettingKind::FlapRumble => change_percentage(&mut settings.flap_rumble, step),
+            SettingKind::ScoreRumble => change_percentage(&mut settings.score_rumble, step),
+            SettingKind::DeathRumble => change_percentage(&mut settings.death_rumble, step),
+        }
+    }
+}
+
+#[derive(Debug, Component)]
+struct SettingsMenu;
+
+#[derive(Debug, Component)]
+struct SettingButton {
+    kind: SettingKind,
+    step: f32,
+}
+
+#[derive(Debug, Component)]
+struct SettingValueLabel(SettingKind);
+
+#[derive(Debug, Component)]
+struct SettingsBackButton;
+
+#[derive(Debug, Component, ChangeStateButton)]
+#[target_state(Controls)]
+struct ControlsButton;
+
+fn spawn_setting_button(parent: &mut ChildBuilder, kind: SettingKind, step: f32, text: String) {
+    parent
+        .spawn((
+            focusable_button(
+                ButtonBundle {
+                    background_color: DEFAULT_BUTTON_COLOR.into(),
+                    style: Style {
+                        padding: UiRect::axes(Val::Px(15.0), Val::Px(5.0)),
+                        min_width: Val::Px(40.0),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                format!("{kind:?}SettingButton"),
+            ),
+            SettingButton { kind, step },
+        ))
+        .with_children(|parent| {
+            let mut text = parent.spawn(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font_size: 20.0,
+                    ..default()
+                },
+            ));
+            if !kind.is_percentage() {
+                text.insert(SettingValueLabel(kind));
+            }
+        });
+}
+
+fn spawn_settings_menu(mut commands: Commands, settings: Res<Settings>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    height: Val::Percent(100.0),
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(50.0)),
+                    row_gap: Val::Px(15.0),
+                    ..default()
+                },
+                ..default()
+            },
+            SettingsMenu,
+            Name::new("SettingsMenu"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "SETTINGS",
+                    TextStyle {
+                        font_size: 50.0,
+                        ..default()
+                    },
+                ),
+                Name::new("SettingsMenuTitle"),
+            ));
+
+            for kind in SettingKind::all() {
+                parent
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Px(500.0),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::SpaceBetween,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        Name::new(format!("{kind:?}SettingRow")),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            kind.label(),
+                            TextStyle {
+                                font_size: 25.0,
+                                ..default()
+                            },
+                        ));
+
+                        if !kind.is_percentage() {
+                            spawn_setting_button(parent, kind, 1.0, kind.value(&settings));
+                            return;
+                        }
+
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    align_items: AlignItems::Center,
+                                    column_gap: Val::Px(10.0),
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                spawn_setting_button(parent, kind, -1.0, "-".to_string());
+                                parent.spawn((
+                                    TextBundle::from_section(
+                                        kind.value(&settings),
+                                        TextStyle {
+                                            font_size: 25.0,
+                                            ..default()
+                                        },
+                                    ),
+                                    SettingValueLabel(kind),
+                                ));
+                                spawn_setting_button(parent, kind, 1.0, "+".to_string());
+                            });
+                    });
+            }
+
+            parent
+                .spawn(change_state_button(
+                    ButtonBundle {
+                        background_color: DEFAULT_BUTTON_COLOR.into(),
+                        style: Style {
+                            padding: UiRect::axes(Val::Px(20.0), Val::Px(10.0)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    ControlsButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "CONTROLS",
+                            TextStyle {
+                                font_size: 25.0,
+                                ..default()
+                            },
+                        ),
+                        Name::new("ControlsButtonText"),
+                    ));
+                });
+
+            parent
+                .spawn((
+                    focusable_button(
+                        ButtonBundle {
+                            background_color: DEFAULT_BUTTON_COLOR.into(),
+                            style: Style {
+                                padding: UiRect::all(Val::Px(20.0)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        "SettingsBackButton".to_string(),
+                    ),
+                    SettingsBackButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "BACK",
+                            TextStyle {
+                                font_size: 35.0,
+                                ..default()
+                            },
+                        ),
+                        Name::new("SettingsBackButtonText"),
+                    ));
+                });
+        });
+}
+
+//a click, or Confirm while the button has focus
+fn setting_button_pressed(
+    interaction_query: Query<(&Interaction, &SettingButton), Changed<Interaction>>,
+    query_buttons: Query<&SettingButton>,
+    selected_button: Res<SelectedButton>,
+    input: ActionInput,
+    mut settings: ResMut<Settings>,
+) {
+    let clicked = interaction_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, button)| button);
+    let confirmed = selected_button
+        .entity()
+        .filter(|_| input.just_pressed(Action::Confirm))
+        .and_then(|entity| query_buttons.get(entity).ok());
+    for button in clicked.chain(confirmed) {
+        button.kind.change(&mut settings, button.step);
+    }
+}
+
+fn update_setting_labels(
+    settings: Res<Settings>,
+    mut query_labels: Query<(&mut Text, &SettingValueLabel)>,
+) {
+    for (mut text, label) in query_labels.iter_mut() {
+        let section = text.sections.first_mut().expect("to have a TextSection");
+        section.value = label.0.value(&settings);
+    }
+}
+
+//a click, or Confirm while the button has focus
+fn back_button_pressed(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<SettingsBackButton>)>,
+    query_buttons: Query<(), With<SettingsBackButton>>,
+    selected_button: Res<SelectedButton>,
+    input: ActionInput,
+    settings_origin: Res<SettingsOrigin>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let clicked = interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed);
+    let confirmed = input.just_pressed(Action::Confirm)
+        && selected_button
+            .entity()
+            .is_some_and(|entity| query_buttons.contains(entity));
+    if clicked || confirmed {
+        next_state.set(settings_origin.0);
+    }
+}
+
+fn go_back(settings_origin: Res<SettingsOrigin>, mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(settings_origin.0);
+}
+
+fn despawn_settings_menu(mut commands: Commands, query: Query<Entity, With<SettingsMenu>>) {
+    for menu in query.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+}
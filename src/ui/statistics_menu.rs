@@ -0,0 +1,308 @@
+use bevy::prelude::*;
+
+use super::buttons::{
+    change_state_button, focusable_button, Action, ChangeStateButton, SelectedButton,
+    DEFAULT_BUTTON_COLOR,
+};
+use crate::input::ActionInput;
+use crate::screen_entity::DeathCause;
+use crate::state::gamestate::GameState;
+use crate::state::save_file::SaveData;
+use crate::state::statistics::{SessionStatistics, Statistics};
+
+pub struct StatisticsMenuPlugin;
+
+impl Plugin for StatisticsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StatisticsScope>()
+            .add_systems(OnEnter(GameState::Statistics), spawn_statistics_menu)
+            .add_systems(
+                Update,
+                (
+                    toggle_scope,
+                    update_statistics_labels.run_if(resource_changed::<StatisticsScope>()),
+                    StatisticsBackButton::button_pressed_system,
+                    StatisticsBackButton::action_pressed_system,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Statistics)),
+            )
+            .add_systems(OnExit(GameState::Statistics), despawn_statistics_menu);
+    }
+}
+
+#[derive(Debug, Resource, Default, Clone, Copy, PartialEq, Eq)]
+enum StatisticsScope {
+    #[default]
+    AllTime,
+    Session,
+}
+
+impl StatisticsScope {
+    fn label(self) -> &'static str {
+        match self {
+            StatisticsScope::AllTime => "ALL TIME",
+            StatisticsScope::Session => "THIS SESSION",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StatisticKind {
+    Games,
+    Flaps,
+    MoaiPassed,
+    TimePlayed,
+    AverageScore,
+    MedianScore,
+    LongestRun,
+    Deaths(DeathCause),
+}
+
+impl StatisticKind {
+    fn all() -> [StatisticKind; 10] {
+        [
+            StatisticKind::Games,
+            StatisticKind::Flaps,
+            StatisticKind::MoaiPassed,
+            StatisticKind::TimePlayed,
+            StatisticKind::AverageScore,
+            StatisticKind::MedianScore,
+            StatisticKind::LongestRun,
+            StatisticKind::Deaths(DeathCause::Floor),
+            StatisticKind::Deaths(DeathCause::MoaiTop),
+            StatisticKind::Deaths(DeathCause::MoaiBottom),
+        ]
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            StatisticKind::Games => "Games played",
+            StatisticKind::Flaps => "Flaps",
+            StatisticKind::MoaiPassed => "Moai passed",
+            StatisticKind::TimePlayed => "Time played",
+            StatisticKind::AverageScore => "Average score",
+            StatisticKind::MedianScore => "Median score",
+            StatisticKind::LongestRun => "Longest run",
+            StatisticKind::Deaths(DeathCause::Floor) => "Fell to the ground",
+            StatisticKind::Deaths(DeathCause::MoaiTop) => "Hit an upper moai",
+            StatisticKind::Deaths(DeathCause::MoaiBottom) => "Hit a lower moai",
+        }
+    }
+
+    fn value(self, statistics: &Statistics) -> String {
+        let score = |score: Option<f32>| match score {
+            Some(score) => format!("{score:.1}"),
+            None => "-".to_string(),
+        };
+
+        match self {
+            StatisticKind::Games => statistics.games().to_string(),
+            StatisticKind::Flaps => statistics.flaps().to_string(),
+            StatisticKind::MoaiPassed => statistics.moai_passed().to_string(),
+            StatisticKind::TimePlayed => {
+                let seconds = statistics.seconds_played() as u64;
+                format!(
+                    "{}:{:02}:{:02}",
+                    seconds / 3600,
+                    seconds / 60 % 60,
+                    seconds % 60
+                )
+            }
+            StatisticKind::AverageScore => score(statistics.average_score()),
+            StatisticKind::MedianScore => score(statistics.median_score()),
+            StatisticKind::LongestRun => format!("{:.1}s", statistics.longest_run_seconds()),
+            StatisticKind::Deaths(cause) => statistics.deaths(cause).to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Component)]
+struct StatisticsMenu;
+
+#[derive(Debug, Component)]
+struct StatisticValueLabel(StatisticKind);
+
+#[derive(Debug, Component)]
+struct ScopeButton;
+
+#[derive(Debug, Component)]
+struct ScopeLabel;
+
+#[derive(Debug, Component, ChangeStateButton)]
+#[action(Back)]
+#[target_state(MainMenu)]
+struct StatisticsBackButton;
+
+fn spawn_statistics_menu(mut commands: Commands, mut scope: ResMut<StatisticsScope>) {
+    //fills in the values once the menu is spawned
+    scope.set_changed();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    height: Val::Percent(100.0),
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(50.0)),
+                    row_gap: Val::Px(10.0),
+                    ..default()
+                },
+                ..default()
+            },
+            StatisticsMenu,
+            Name::new("StatisticsMenu"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "STATISTICS",
+                    TextStyle {
+                        font_size: 50.0,
+                        ..default()
+                    },
+                ),
+                Name::new("StatisticsTitle"),
+            ));
+
+            parent
+                .spawn((
+                    focusable_button(
+                        ButtonBundle {
+                            background_color: DEFAULT_BUTTON_COLOR.into(),
+                            style: Style {
+                                padding: UiRect::axes(Val::Px(20.0), Val::Px(10.0)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        "ScopeButton".to_string(),
+                    ),
+                    ScopeButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            scope.label(),
+                            TextStyle {
+                                font_size: 25.0,
+                                ..default()
+                            },
+                        ),
+                        ScopeLabel,
+                    ));
+                });
+
+            for kind in StatisticKind::all() {
+                parent
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Px(500.0),
+                                justify_content: JustifyContent::SpaceBetween,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        Name::new(format!("{kind:?}StatisticRow")),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            kind.label(),
+                            TextStyle {
+                                font_size: 25.0,
+                                ..default()
+                            },
+                        ));
+                        parent.spawn((
+                            TextBundle::from_section(
+                                "",
+                                TextStyle {
+                                    font_size: 25.0,
+                                    ..default()
+                                },
+                            ),
+                            StatisticValueLabel(kind),
+                        ));
+                    });
+            }
+
+            parent
+                .spawn(change_state_button(
+                    ButtonBundle {
+                        background_color: DEFAULT_BUTTON_COLOR.into(),
+                        style: Style {
+                            padding: UiRect::all(Val::Px(20.0)),
+                            margin: UiRect::top(Val::Px(20.0)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    StatisticsBackButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "BACK",
+                            TextStyle {
+                                font_size: 35.0,
+                                ..default()
+                            },
+                        ),
+                        Name::new("StatisticsBackButtonText"),
+                    ));
+                });
+        });
+}
+
+//a click, or Confirm while the button has focus
+fn toggle_scope(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ScopeButton>)>,
+    query_buttons: Query<(), With<ScopeButton>>,
+    selected_button: Res<SelectedButton>,
+    input: ActionInput,
+    mut scope: ResMut<StatisticsScope>,
+) {
+    let clicked = interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed);
+    let confirmed = input.just_pressed(Action::Confirm)
+        && selected_button
+            .entity()
+            .is_some_and(|entity| query_buttons.contains(entity));
+    if clicked || confirmed {
+        *scope = match *scope {
+            StatisticsScope::AllTime => StatisticsScope::Session,
+            StatisticsScope::Session => StatisticsScope::AllTime,
+        };
+    }
+}
+
+fn update_statistics_labels(
+    scope: Res<StatisticsScope>,
+    save_data: Res<SaveData>,
+    session_statistics: Res<SessionStatistics>,
+    mut query_values: Query<(&mut Text, &StatisticValueLabel), Without<ScopeLabel>>,
+    mut query_scope: Query<&mut Text, With<ScopeLabel>>,
+) {
+    let statistics = match *scope {
+        StatisticsScope::AllTime => save_data.statistics(),
+        StatisticsScope::Session => &session_statistics.0,
+    };
+    for (mut text, label) in query_values.iter_mut() {
+        let section = text.sections.first_mut().expect("to have a TextSection");
+        section.value = label.0.value(statistics);
+    }
+    for mut text in query_scope.iter_mut() {
+        let section = text.sections.first_mut().expect("to have a TextSection");
+        section.value = scope.label().to_string();
+    }
+}
+
+fn despawn_statistics_menu(mut commands: Commands, query: Query<Entity, With<StatisticsMenu>>) {
+    for menu in query.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::VecDeque;
+
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent};
+use bevy::prelude::*;
+
+use crate::state::achievements::AchievementUnlocked;
+use crate::state::gamescore::ScoreEvent;
+use crate::state::save_file::SaveError;
+use crate::state::settings::Settings;
+
+const MAX_VISIBLE_TOASTS: usize = 3;
+const TOAST_SECONDS: f32 = 4.0;
+const TOAST_SLIDE_SECONDS: f32 = 0.25;
+const TOAST_SLIDE_DISTANCE: f32 = 40.0;
+//at the end of the toast's lifetime
+const TOAST_FADE_SECONDS: f32 = 0.5;
+const TOAST_BACKGROUND_COLOR: Color = Color::rgba(0.1, 0.1, 0.1, 0.85);
+
+pub struct ToastsPlugin;
+
+impl Plugin for ToastsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Toasts>()
+            .add_systems(Startup, spawn_toast_container)
+            .add_systems(
+                Update,
+                (
+                    (
+                        queue_high_score_toasts,
+                        queue_achievement_toasts,
+                        queue_gamepad_toasts,
+                        queue_save_error_toasts,
+                    ),
+                    show_toasts,
+                    animate_toasts,
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Warning,
+}
+
+impl ToastKind {
+    fn color(self) -> Color {
+        match self {
+            ToastKind::Info => Color::WHITE,
+            ToastKind::Success => Color::GOLD,
+            ToastKind::Warning => Color::YELLOW,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    kind: ToastKind,
+    title: String,
+    detail: Option<String>,
+}
+
+impl Toast {
+    pub fn new(kind: ToastKind, title: impl Into<String>) -> Toast {
+        Toast {
+            kind,
+            title: title.into(),
+            detail: None,
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Toast {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+//Toasts waiting to be shown, oldest first. Only a few are on screen at once,
+//the rest wait here until one of them expires.
+#[derive(Debug, Resource, Default)]
+pub struct Toasts(VecDeque<Toast>);
+
+impl Toasts {
+    pub fn push(&mut self, toast: Toast) {
+        self.0.push_back(toast);
+    }
+}
+
+//never despawned, so toasts stay up across state changes
+#[derive(Debug, Component)]
+struct ToastContainer;
+
+#[derive(Debug, Component)]
+struct ToastNode {
+    age: f32,
+}
+
+fn spawn_toast_container(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                top: Val::Px(20.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            z_index: ZIndex::Global(20),
+            ..default()
+        },
+        ToastContainer,
+        Name::new("ToastContainer"),
+    ));
+}
+
+fn queue_high_score_toasts(mut score_events: EventReader<ScoreEvent>, mut toasts: ResMut<Toasts>) {
+    for event in score_events.read() {
+        if let ScoreEvent::NewHighScore = event {
+            toasts.push(Toast::new(ToastKind::Success, "New high score!"));
+        }
+    }
+}
+
+fn queue_achievement_toasts(
+    mut unlocked_events: EventReader<AchievementUnlocked>,
+    mut toasts: ResMut<Toasts>,
+) {
+    for unlocked in unlocked_events.read() {
+        toasts.push(
+            Toast::new(
+                ToastKind::Success,
+                format!("Achievement unlocked: {}", unlocked.name),
+            )
+            .with_detail(unlocked.description.clone()),
+        );
+    }
+}
+
+fn queue_gamepad_toasts(
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    mut toasts: ResMut<Toasts>,
+) {
+    for event in connection_events.read() {
+        let toast = match &event.connection {
+            GamepadConnection::Connected(info) => {
+                Toast::new(ToastKind::Info, "Controller connected").with_detail(info.name.clone())
+            }
+            GamepadConnection::Disconnected => {
+                Toast::new(ToastKind::Info, "Controller disconnected")
+            }
+        };
+        toasts.push(toast);
+    }
+}
+
+fn queue_save_error_toasts(mut error_events: EventReader<SaveError>, mut toasts: ResMut<Toasts>) {
+    for error in error_events.read() {
+        toasts.push(Toast::new(ToastKind::Warning, error.0.clone()));
+    }
+}
+
+fn show_toasts(
+    mut commands: Commands,
+    mut toasts: ResMut<Toasts>,
+    query_container: Query<Entity, With<ToastContainer>>,
+    query_toasts: Query<(), With<ToastNode>>,
+) {
+    let Ok(container) = query_container.get_single() else {
+        return;
+    };
+    let free_slots = MAX_VISIBLE_TOASTS.saturating_sub(query_toasts.iter().count());
+    for _ in 0..free_slots {
+        let Some(toast) = toasts.0.pop_front() else {
+            return;
+        };
+        commands.entity(container).with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            padding: UiRect::axes(Val::Px(30.0), Val::Px(15.0)),
+                            ..default()
+                        },
+                        background_color: TOAST_BACKGROUND_COLOR.into(),
+                        ..default()
+                    },
+                    ToastNode { age: 0.0 },
+                    Name::new("Toast"),
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        toast.title,
+                        TextStyle {
+                            font_size: 30.0,
+                            color: toast.kind.color(),
+                            ..default()
+                        },
+                    ));
+                    if let Some(detail) = toast.detail {
+                        parent.spawn(TextBundle::from_section(
+                            detail,
+                            TextStyle {
+                                font_size: 20.0,
+                                ..default()
+                            },
+                        ));
+                    }
+                });
+        });
+    }
+}
+
+//slides in from above and fades out, or just appears and disappears with
+//reduced motion
+fn animate_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut query_toasts: Query<(
+        Entity,
+        &mut ToastNode,
+        &mut Style,
+        &mut BackgroundColor,
+        &Children,
+    )>,
+    mut query_text: Query<&mut Text>,
+) {
+    for (entity, mut toast, mut style, mut background_color, children) in query_toasts.iter_mut() {
+        toast.age += time.delta_seconds();
+        if toast.age >= TOAST_SECONDS {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let (offset, alpha) = if settings.reduced_motion {
+            (0.0, 1.0)
+        } else {
+            let slide = (toast.age / TOAST_SLIDE_SECONDS).min(1.0);
+            let fade = ((TOAST_SECONDS - toast.age) / TOAST_FADE_SECONDS).min(1.0);
+            //ease out, fast at first and settling into place
+            let offset = -TOAST_SLIDE_DISTANCE * (1.0 - slide).powi(2);
+            (offset, slide.min(fade))
+        };
+
+        style.top = Val::Px(offset);
+        background_color.0.set_a(TOAST_BACKGROUND_COLOR.a() * alpha);
+        let mut texts = query_text.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            for section in text.sections.iter_mut() {
+                section.style.color.set_a(alpha);
+            }
+        }
+    }
+}